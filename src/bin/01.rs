@@ -1,18 +1,12 @@
-use regex::Regex;
+use advent_of_code::parse;
 use std::collections::HashMap;
 use std::iter::zip;
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, scale = advent_of_code::template::scale::repeat_lines);
 
 fn parse(input: &str) -> Option<(Vec<u32>, Vec<u32>)> {
-    let re = Regex::new(r"(\d*)(\s*)(\d*)\n").ok()?;
-    let mut left: Vec<u32> = vec![];
-    let mut right: Vec<u32> = vec![];
-    for m in re.captures_iter(input) {
-        left.push(m.get(1)?.as_str().parse().ok()?);
-        right.push(m.get(3)?.as_str().parse().ok()?);
-    }
-    Some((left, right))
+    let pairs = parse::each_line(input, parse::unsigned_n::<u32, 2>).ok()?;
+    Some(pairs.into_iter().map(|[l, r]| (l, r)).unzip())
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
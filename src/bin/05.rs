@@ -1,124 +1,94 @@
-use regex::Regex;
-use std::collections::{HashMap, HashSet};
+use advent_of_code::{
+    order::Rules,
+    parse::{self, ParseError},
+    template::rng::Rng,
+};
+use anyhow::Result;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, scale = scale_input);
 
 /*
- * Returns a hashmap mapping the page number to a set
- * containing all pages that should be AFTER
+ * Synthesizes an input with `factor` times as many pages, and manuals `factor` times as long.
+ * Pages follow a random total order, with a rule for every pair of pages like the real input
  */
-fn parse_order(lines: &mut dyn Iterator<Item = &str>) -> Option<HashMap<u32, HashSet<u32>>> {
-    let mut res = HashMap::<u32, HashSet<u32>>::new();
-    let re = Regex::new(r"(\d*)\|(\d*)").ok()?;
+fn scale_input(input: &str, factor: usize) -> String {
+    let (order, manuals) = parse(input).unwrap();
 
-    for l in lines {
-        if l.len() == 0 {
-            break;
-        }
+    let pages = order.nodes().len().max(1) * factor;
+    let mut rng = Rng::new(factor as u64);
 
-        let captures = re.captures(l)?;
-        let p = |x| Some(captures.get(x)?.as_str().parse::<u32>().ok()?);
-        let a: u32 = p(1)?;
-        let b: u32 = p(2)?;
-
-        match res.get_mut(&a) {
-            Some(set) => {
-                set.insert(b);
-            }
-            None => {
-                let mut set = HashSet::<u32>::new();
-                set.insert(b);
-                res.insert(a, set);
-            }
-        }
+    // Fisher-Yates shuffle of the pages gives the order
+    let mut ranked: Vec<u32> = (10..10 + pages as u32).collect();
+    for i in (1..ranked.len()).rev() {
+        ranked.swap(i, rng.index(i + 1));
     }
 
-    Some(res)
-}
-
-/*
- * O(n^2) search to check if manual is correctly indexed
- */
-fn check_manual(order: &HashMap<u32, HashSet<u32>>, manual: &Vec<u32>) -> Option<bool> {
-    for (i1, p1) in manual[1..].iter().enumerate() {
-        if let Some(set) = order.get(p1) {
-            for p2 in &manual[..i1 + 1] {
-                if set.contains(p2) {
-                    return Some(false);
-                }
-            }
+    let mut res = String::new();
+    for (i, a) in ranked.iter().enumerate() {
+        for b in &ranked[i + 1..] {
+            res.push_str(&format!("{a}|{b}\n"));
         }
     }
-    Some(true)
-}
+    res.push('\n');
 
-/*
- * None if manual is already sorted, Some(man) with man sorted else
- */
-fn sort_manual(order: &HashMap<u32, HashSet<u32>>, mut manual: Vec<u32>) -> Option<Vec<u32>> {
-    let mut sorted = true;
-    for i in 1..manual.len() {
-        for j in 0..i {
-            if let Some(set) = order.get(manual.get(i)?) {
-                if set.contains(manual.get(j)?) {
-                    sorted = false;
-                    manual.swap(i, j);
-                }
-            }
+    for manual in manuals {
+        // Manuals have an odd length, so they have a middle page
+        let len = ((manual.len() * factor) | 1).min(pages);
+        let mut manual: Vec<u32> = ranked.clone();
+        for i in 0..len {
+            manual.swap(i, i + rng.index(pages - i));
         }
+        let manual: Vec<String> = manual[..len].iter().map(u32::to_string).collect();
+        res.push_str(&manual.join(","));
+        res.push('\n');
     }
-    if sorted {
-        None
-    } else {
-        Some(manual)
-    }
+
+    res
 }
 
-fn parse_manuals<'a>(lines: impl Iterator<Item = &'a str>) -> Option<Vec<Vec<u32>>> {
-    lines
-        .map(|l| {
-            l.split(',')
-                .map(|x| str::parse::<u32>(x).ok())
-                .collect::<Option<Vec<u32>>>()
-        })
-        .collect()
+fn parse_rule(line: &str) -> Result<(u32, u32), ParseError> {
+    let [before, after] = parse::unsigned_n(line)?;
+    Ok((before, after))
 }
 
-fn sum_valid_manuals(
-    lines: &mut dyn Iterator<Item = &str>,
-    order: HashMap<u32, HashSet<u32>>,
-) -> Option<u32> {
-    let mut res = 0;
-    for l in lines {
-        let manual = l
-            .split(',')
-            .map(|x| str::parse::<u32>(x).ok())
-            .collect::<Option<Vec<u32>>>()?;
-        res += (check_manual(&order, &manual)? as u32) * manual.get(manual.len() >> 1)?;
-    }
-    Some(res)
+/*
+ * Returns the ordering rules, and the manuals
+ */
+fn parse(input: &str) -> Result<(Rules<u32>, Vec<Vec<u32>>)> {
+    let mut blocks = parse::blocks(input);
+    let order = parse::each_line(blocks.next().unwrap_or_default(), parse_rule)?;
+    let manuals = parse::each_line(blocks.next().unwrap_or_default(), parse::list)?;
+    Ok((order.into_iter().collect(), manuals))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut input_iter = input.lines();
-    let order = parse_order(&mut input_iter)?;
-    sum_valid_manuals(&mut input_iter, order)
+fn middle(manual: &[u32]) -> u32 {
+    manual[manual.len() >> 1]
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut input_iter = input.lines();
-    let order = parse_order(&mut input_iter)?;
-    let manuals = parse_manuals(input_iter)?;
+pub fn part_one(input: &str) -> Option<u32> {
+    let (order, manuals) = parse(input).unwrap();
     Some(
         manuals
-            .into_iter()
-            .fold(0, |acc, m| match sort_manual(&order, m) {
-                Some(manual) => acc + manual.get(manual.len() >> 1).unwrap(),
-                None => acc,
-            }),
+            .iter()
+            .filter(|m| order.is_sorted(m))
+            .map(|m| middle(m))
+            .sum(),
     )
 }
 
+/*
+ * Only the rules between the pages of a manual matter : these have no cycle, even though
+ * all the rules together do
+ */
+pub fn part_two(input: &str) -> Option<u32> {
+    let (order, manuals) = parse(input).unwrap();
+    let mut res = 0;
+    for manual in manuals.iter().filter(|m| !order.is_sorted(m)) {
+        res += middle(&order.topological_sort(manual).unwrap());
+    }
+    Some(res)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,4 +104,15 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(123));
     }
+
+    #[test]
+    fn test_scale_input() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let scaled = scale_input(&input, 4);
+        let (order, manuals) = parse(&scaled).unwrap();
+        assert_eq!(order.nodes().len(), 4 * 7);
+        assert_eq!(order.len(), 4 * 7 * (4 * 7 - 1) / 2);
+        assert_eq!(manuals.len(), 6);
+        assert!(part_two(&scaled).is_some());
+    }
 }
@@ -1,5 +1,8 @@
-use anyhow::{anyhow, Context, Result};
-use ndarray::{Array, Array2};
+use advent_of_code::{
+    geometry::{Direction, Position},
+    grid::Grid,
+};
+use anyhow::{anyhow, Result};
 use std::collections::HashSet;
 use std::iter::Iterator;
 
@@ -9,80 +12,33 @@ const EXPLORED_CELL: char = 'X';
 const WALL_CELL: char = '#';
 const FREE_CELL: char = '.';
 
-#[derive(PartialEq, Clone, Eq, Hash)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-fn char_to_dir(c: char) -> Option<Direction> {
-    match c {
-        '^' => Some(Direction::Up),
-        '>' => Some(Direction::Right),
-        'v' => Some(Direction::Down),
-        '<' => Some(Direction::Left),
-        _ => None,
-    }
-}
-
 #[derive(PartialEq, Clone, Eq, Hash)]
 struct Guard {
     dir: Direction,
-    x: usize,
-    y: usize,
-}
-
-impl Guard {
-    pub fn turn(&mut self) {
-        self.dir = match self.dir {
-            Direction::Up => Direction::Right,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-        };
-    }
+    pos: Position,
 }
 
 #[derive(Clone)]
 struct Map {
     guard: Guard,
-    arr: Array2<char>,
+    arr: Grid<char>,
 }
 
 impl Map {
     pub fn parse(input: &str) -> Result<Map> {
-        let arr = Array::from_iter(input.chars().filter(|x| *x != '\n'));
-        let arr = arr
-            .into_shape_with_order((
-                input
-                    .chars()
-                    .fold(0, |acc, elem| acc + ((elem == '\n') as usize)),
-                input
-                    .lines()
-                    .next()
-                    .ok_or(anyhow!("No more than 1 line in input..."))?
-                    .len(),
-            ))
-            .context("Bad array shape")?;
-
-        for row in 0..arr.dim().0 {
-            for col in 0..arr.dim().1 {
-                if let Some(dir) = char_to_dir(arr[[row, col]]) {
-                    return Ok(Map {
-                        guard: Guard {
-                            dir,
-                            x: col,
-                            y: row,
-                        },
-                        arr,
-                    });
-                }
-            }
-        }
+        let arr = Grid::<char>::parse(input)?;
 
-        Err(anyhow!("Guard not found !"))
+        let (pos, dir) = arr
+            .iter()
+            .find_map(|(pos, c)| Some((pos, Direction::try_from(*c).ok()?)))
+            .ok_or(anyhow!("Guard not found !"))?;
+        Ok(Map {
+            guard: Guard {
+                dir,
+                pos: pos.into(),
+            },
+            arr,
+        })
     }
 
     /*
@@ -90,29 +46,23 @@ impl Map {
      * Returns false if guard goes out of bound
      */
     fn step(&mut self) -> Result<bool> {
-        let (next_y, next_x) = match self.guard.dir {
-            Direction::Up => (self.guard.y.wrapping_sub(1), self.guard.x),
-            Direction::Right => (self.guard.y, self.guard.x.wrapping_add(1)),
-            Direction::Down => (self.guard.y.wrapping_add(1), self.guard.x),
-            Direction::Left => (self.guard.y, self.guard.x.wrapping_sub(1)),
-        };
+        let next = self.guard.pos.step(self.guard.dir, self.arr.size());
 
         let curr_c = self
             .arr
-            .get_mut((self.guard.y, self.guard.x))
+            .get_mut(self.guard.pos.into())
             .ok_or(anyhow!("Bad guard position"))?;
         *curr_c = EXPLORED_CELL;
 
-        if let Some(next_c) = self.arr.get((next_y, next_x)) {
-            let next_c = *next_c;
+        if let Some(next) = next {
+            let next_c = self.arr[next];
             return match next_c {
                 FREE_CELL | EXPLORED_CELL => {
-                    self.guard.x = next_x;
-                    self.guard.y = next_y;
+                    self.guard.pos = next;
                     return Ok(true);
                 }
                 WALL_CELL => {
-                    self.guard.turn();
+                    self.guard.dir = self.guard.dir.turn_right();
                     return Ok(true);
                 }
                 _ => Err(anyhow!("Unknown char {}", next_c)),
@@ -128,10 +78,7 @@ impl Map {
      */
     pub fn evolve(&mut self) -> Result<u32> {
         while self.step()? {}
-        Ok(self
-            .arr
-            .iter()
-            .fold(0u32, |acc, elem| acc + ((*elem == EXPLORED_CELL) as u32)))
+        Ok(self.arr.find_all(&EXPLORED_CELL).count() as u32)
     }
 
     /*
@@ -164,18 +111,13 @@ pub fn part_two(input: &str) -> Option<u32> {
     map_init.evolve().unwrap();
 
     // For each explored position (except initial one), try to put an obstacle
-    *map_init.arr.get_mut((map.guard.y, map.guard.x)).unwrap() = FREE_CELL;
-    for row in 0..map_init.arr.dim().0 {
-        for col in 0..map_init.arr.dim().1 {
-            let c = *map_init.arr.get((row, col)).unwrap();
-            // If explored cell, try to block the guard and see what happens
-            if c == EXPLORED_CELL {
-                let mut map_clone = map.clone();
-                *map_clone.arr.get_mut((row, col)).unwrap() = WALL_CELL;
-                if map_clone.evolve_is_stuck().unwrap() {
-                    res += 1;
-                }
-            }
+    map_init.arr[map.guard.pos] = FREE_CELL;
+    // If explored cell, try to block the guard and see what happens
+    for pos in map_init.arr.find_all(&EXPLORED_CELL) {
+        let mut map_clone = map.clone();
+        map_clone.arr[pos] = WALL_CELL;
+        if map_clone.evolve_is_stuck().unwrap() {
+            res += 1;
         }
     }
 
@@ -1,6 +1,9 @@
 use anyhow::{anyhow, Result};
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, variants {
+    part_one => [part_one_reverse],
+    part_two => [part_two_reverse],
+}, scale = advent_of_code::template::scale::repeat_lines);
 
 #[derive(Debug)]
 struct Equation {
@@ -62,15 +65,57 @@ impl Equation {
         }
     }
 
+    /*
+     * Same search, but undoing operations from the result down to the first term:
+     * a product must divide the result and a concatenation must be one of its suffixes,
+     * which prunes most branches early
+     */
+    fn is_solvable_reverse(&self, res: u64, len: usize, concat: bool) -> bool {
+        if len == 1 {
+            return res == self.terms[0];
+        }
+        let val = self.terms[len - 1];
+        // Terms are positive, so no operation makes the result smaller than the last term
+        if val == 0 || res < val {
+            return false;
+        }
+        let pow = 10u64.pow(val.ilog10() + 1);
+        (res.is_multiple_of(val) && self.is_solvable_reverse(res / val, len - 1, concat))
+            || self.is_solvable_reverse(res - val, len - 1, concat)
+            || (concat
+                && (res - val).is_multiple_of(pow)
+                && self.is_solvable_reverse((res - val) / pow, len - 1, concat))
+    }
+
     pub fn is_solvable_p1(&self) -> bool {
-        self.is_solvable_core_p1(self.res, 0, 0)
+        self.is_solvable_core_p1(self.res, 1, self.terms[0])
     }
 
     pub fn is_solvable_p2(&self) -> bool {
-        self.is_solvable_core_p2(self.res, 0, 0)
+        self.is_solvable_core_p2(self.res, 1, self.terms[0])
     }
 }
 
+pub fn part_one_reverse(input: &str) -> Option<u64> {
+    let equations = input.lines().map(|l| Equation::parse(l).unwrap());
+    Some(
+        equations
+            .filter(|e| e.is_solvable_reverse(e.res, e.terms.len(), false))
+            .map(|e| e.res)
+            .sum(),
+    )
+}
+
+pub fn part_two_reverse(input: &str) -> Option<u64> {
+    let equations = input.lines().map(|l| Equation::parse(l).unwrap());
+    Some(
+        equations
+            .filter(|e| e.is_solvable_reverse(e.res, e.terms.len(), true))
+            .map(|e| e.res)
+            .sum(),
+    )
+}
+
 pub fn part_one(input: &str) -> Option<u64> {
     Some(
         input
@@ -96,6 +141,10 @@ pub fn part_two(input: &str) -> Option<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::{
+        differential::{check, ListOf},
+        rng::Rng,
+    };
 
     #[test]
     fn test_part_one() {
@@ -103,9 +152,90 @@ mod tests {
         assert_eq!(result, Some(3749));
     }
 
+    #[test]
+    fn test_part_one_reverse() {
+        let result = part_one_reverse(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(3749));
+    }
+
+    #[test]
+    fn test_part_two_reverse() {
+        let result = part_two_reverse(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(11387));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(11387));
     }
+
+    /*
+     * Random equations : the result is either computed from the terms with random
+     * operators (concatenation included), or random
+     */
+    fn equations() -> ListOf<(u64, Vec<u64>)> {
+        ListOf {
+            len: 1..=10,
+            separator: "\n",
+            item: |rng: &mut Rng| {
+                let len = rng.range(1..=6) as usize;
+                let terms: Vec<u64> = (0..len).map(|_| rng.range(1..=20)).collect();
+                let res = if rng.chance(0.5) {
+                    terms[1..]
+                        .iter()
+                        .fold(terms[0], |acc, &t| match rng.range(0..=2) {
+                            0 => acc + t,
+                            1 => acc * t,
+                            _ => acc * 10u64.pow(t.ilog10() + 1) + t,
+                        })
+                } else {
+                    rng.range(1..=1000)
+                };
+                (res, terms)
+            },
+            render: |(res, terms)| {
+                let terms: Vec<String> = terms.iter().map(u64::to_string).collect();
+                format!("{}: {}", res, terms.join(" "))
+            },
+            shrink: |(res, terms)| {
+                let mut smaller = vec![];
+                if terms.len() > 1 {
+                    smaller.push((*res, terms[1..].to_vec()));
+                    smaller.push((*res, terms[..terms.len() - 1].to_vec()));
+                }
+                if *res > 1 {
+                    smaller.push((res / 2, terms.clone()));
+                    smaller.push((res - 1, terms.clone()));
+                }
+                smaller
+            },
+        }
+    }
+
+    #[test]
+    fn test_part_one_differential() {
+        check(
+            DAY,
+            1,
+            &equations(),
+            &[
+                ("part_one", part_one as fn(&str) -> _),
+                ("part_one_reverse", part_one_reverse),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_part_two_differential() {
+        check(
+            DAY,
+            2,
+            &equations(),
+            &[
+                ("part_two", part_two as fn(&str) -> _),
+                ("part_two_reverse", part_two_reverse),
+            ],
+        );
+    }
 }
@@ -1,8 +1,121 @@
-use anyhow::{anyhow, Result};
 use std::fmt::Display;
 
-advent_of_code::solution!(9);
+use advent_of_code::intervals::{FreeList, Span};
+use anyhow::{anyhow, Result};
+
+advent_of_code::solution!(9, variants {
+    part_one => [part_one_blocks],
+    part_two => [part_two_spaces],
+});
+
+/*
+ * The disk as runs of blocks : the files, whose ID is their index, and the free spaces
+ * between them. Blocks are never expanded one by one
+ */
+struct DiskMap {
+    files: Vec<Span>,
+    free: Vec<Span>,
+}
+
+impl DiskMap {
+    fn parse(input: &str) -> Result<Self> {
+        let mut disk = DiskMap {
+            files: vec![],
+            free: vec![],
+        };
+
+        let parse_digit = |x: char| -> Result<usize> {
+            let x = x
+                .to_digit(10)
+                .ok_or(anyhow!("Failed to parse digit : \"{}\"", x))?;
+            Ok(x as usize)
+        };
+
+        let mut index = 0;
+        for (count, size) in input.trim_end().chars().enumerate() {
+            let span = Span::new(index, parse_digit(size)?);
+            // Full
+            if count % 2 == 0 {
+                disk.files.push(span);
+            }
+            // Empty
+            else {
+                disk.free.push(span);
+            }
+            index = span.end();
+        }
+
+        Ok(disk)
+    }
+
+    fn len(&self) -> usize {
+        self.files.last().map_or(0, Span::end)
+    }
+}
+
+/*
+ * Fill each free space, from the left, with blocks taken from the end of the last files
+ */
+pub fn part_one(input: &str) -> Option<u64> {
+    let disk = DiskMap::parse(input).unwrap();
+    let mut files = disk.files.clone();
+    let Some(mut back) = files.len().checked_sub(1) else {
+        return Some(0);
+    };
+
+    let mut res = 0;
+    let mut id = 0;
+    while id <= back {
+        // What is left of the file stays in place
+        res += files[id].checksum(id as u64);
+
+        let mut gap = disk.free.get(id).copied().unwrap_or_default();
+        while gap.len > 0 && back > id {
+            let moved = gap.len.min(files[back].len);
+            res += Span::new(gap.start, moved).checksum(back as u64);
+            gap = Span::new(gap.start + moved, gap.len - moved);
+            files[back].len -= moved;
+            if files[back].len == 0 {
+                back -= 1;
+            }
+        }
+        id += 1;
+    }
 
+    Some(res)
+}
+
+/*
+ * Move whole files, from the last one, to the leftmost free space large enough for them
+ */
+pub fn part_two(input: &str) -> Option<u64> {
+    let disk = DiskMap::parse(input).unwrap();
+    let mut free = FreeList::new(disk.len());
+    for span in &disk.free {
+        free.free(*span);
+    }
+
+    let mut res = 0;
+    for (id, file) in disk.files.iter().enumerate().rev() {
+        let file = match free.first_fit(file.len, file.start) {
+            Some(start) => {
+                let moved = Span::new(start, file.len);
+                free.allocate(moved);
+                free.free(*file);
+                moved
+            }
+            None => *file,
+        };
+        res += file.checksum(id as u64);
+    }
+
+    Some(res)
+}
+
+/*
+ * Reference implementations, kept as variants : part one expands the disk block by block,
+ * and part two searches the free spaces linearly for each file
+ */
 #[derive(Clone)]
 enum Chunk {
     FULL(u64),
@@ -10,17 +123,17 @@ enum Chunk {
 }
 
 // For p1 only
-struct DiskMap {
+struct BlockDiskMap {
     chunks: Vec<Chunk>,
 }
 
-impl DiskMap {
+impl BlockDiskMap {
     fn new() -> Self {
-        DiskMap { chunks: vec![] }
+        BlockDiskMap { chunks: vec![] }
     }
 
     fn parse(input: &str) -> Result<Self> {
-        let mut disk = DiskMap::new();
+        let mut disk = BlockDiskMap::new();
 
         let mut count = 0;
         let parse_digit = |x: char| -> Result<u8> {
@@ -30,7 +143,7 @@ impl DiskMap {
             Ok(x)
         };
 
-        let mut input_iter = input.chars();
+        let mut input_iter = input.trim_end().chars();
         while let Some(chunk_size) = input_iter.next() {
             let chunk_size = parse_digit(chunk_size)?;
             // Full
@@ -88,7 +201,7 @@ impl DiskMap {
     }
 }
 
-impl Display for DiskMap {
+impl Display for BlockDiskMap {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for chunk in self.chunks.iter() {
             match chunk {
@@ -161,7 +274,7 @@ impl SpaceDiskMap {
             Ok(x)
         };
 
-        let mut input_iter = input.chars();
+        let mut input_iter = input.trim_end().chars();
         let mut index: usize = 0;
         while let Some(size) = input_iter.next() {
             let size = parse_digit(size)?;
@@ -205,8 +318,8 @@ impl SpaceDiskMap {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let mut disk = DiskMap::parse(input).unwrap();
+pub fn part_one_blocks(input: &str) -> Option<usize> {
+    let mut disk = BlockDiskMap::parse(input).unwrap();
     disk.compress().unwrap();
 
     let mut chunks_iter = disk.chunks.iter().enumerate();
@@ -218,7 +331,7 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(res)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two_spaces(input: &str) -> Option<u64> {
     let mut disk = SpaceDiskMap::parse(input).unwrap();
     disk.compress().unwrap();
 
@@ -239,6 +352,10 @@ pub fn part_two(input: &str) -> Option<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::{
+        differential::{check, no_shrink, ListOf},
+        rng::Rng,
+    };
 
     #[test]
     fn test_part_one() {
@@ -251,4 +368,58 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(2858));
     }
+
+    #[test]
+    fn test_part_one_blocks() {
+        let result = part_one_blocks(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(1928));
+    }
+
+    #[test]
+    fn test_part_two_spaces() {
+        let result = part_two_spaces(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(2858));
+    }
+
+    /*
+     * Random disk maps, as a file and the free space after it, files being at least one block
+     * long as in the puzzle
+     */
+    fn disk_maps() -> ListOf<(u64, u64)> {
+        ListOf {
+            len: 1..=40,
+            separator: "",
+            item: |rng: &mut Rng| (rng.range(1..=9), rng.range(0..=9)),
+            render: |(file, free)| format!("{file}{free}"),
+            shrink: no_shrink,
+        }
+    }
+
+    #[test]
+    fn test_part_one_differential() {
+        check(
+            DAY,
+            1,
+            &disk_maps(),
+            &[
+                ("part_one_blocks", |input| {
+                    part_one_blocks(input).map(|x| x as u64)
+                }),
+                ("part_one", part_one),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_part_two_differential() {
+        check(
+            DAY,
+            2,
+            &disk_maps(),
+            &[
+                ("part_two_spaces", part_two_spaces as fn(&str) -> _),
+                ("part_two", part_two),
+            ],
+        );
+    }
 }
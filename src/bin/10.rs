@@ -1,7 +1,7 @@
 use std::{collections::HashSet, hash::Hash};
 
+use advent_of_code::{grid::Grid, memo::Memo};
 use anyhow::{anyhow, Result};
-use ndarray::{Array, Array2};
 
 advent_of_code::solution!(10);
 
@@ -10,9 +10,7 @@ const TRAILEND: u8 = 9;
 
 #[derive(Debug, Hash, Eq, PartialEq)]
 struct Map {
-    width: usize,
-    height: usize,
-    arr: Array2<u8>,
+    grid: Grid<u8>,
 }
 
 #[derive(Debug, Hash, Eq, PartialEq)]
@@ -40,7 +38,7 @@ impl<'a> Node<'a> {
             );
 
             if let (Some(new_y), Some(new_x)) = (new_y, new_x) {
-                if let Some(&v) = self.map.arr.get((new_y, new_x)) {
+                if let Some(&v) = self.map.grid.get((new_x, new_y)) {
                     if v == self.val + 1 {
                         let node = Node {
                             x: new_x,
@@ -57,7 +55,14 @@ impl<'a> Node<'a> {
         set
     }
 
-    fn explore_all(self) -> usize {
+    /*
+     * Trails from a node do not depend on how it was reached, so they are counted once per node
+     */
+    fn explore_all(self, memo: &mut Memo<(usize, usize), usize>) -> usize {
+        memo.get((self.x, self.y), |memo, _| self.count_trails(memo))
+    }
+
+    fn count_trails(&self, memo: &mut Memo<(usize, usize), usize>) -> usize {
         let mut res = 0;
 
         if self.val == TRAILEND {
@@ -72,7 +77,7 @@ impl<'a> Node<'a> {
             );
 
             if let (Some(new_y), Some(new_x)) = (new_y, new_x) {
-                if let Some(&v) = self.map.arr.get((new_y, new_x)) {
+                if let Some(&v) = self.map.grid.get((new_x, new_y)) {
                     if v == self.val + 1 {
                         let node = Node {
                             x: new_x,
@@ -80,7 +85,7 @@ impl<'a> Node<'a> {
                             val: v,
                             map: self.map,
                         };
-                        res += node.explore_all();
+                        res += node.explore_all(memo);
                     }
                 }
             }
@@ -92,33 +97,25 @@ impl<'a> Node<'a> {
 
 impl Map {
     fn parse(input: &str) -> Result<Self> {
-        let height = input.lines().count();
-        let width = input
-            .find('\n')
-            .ok_or(anyhow!("Did not find more than 1 line in input"))?;
-        let arr: Result<Vec<u8>> = input
-            .chars()
-            .filter(|x| *x != '\n')
-            .map(|x| Ok(x.to_digit(10).ok_or(anyhow!("Failed to parse {}", x))? as u8))
-            .collect();
-        let arr = Array::from_shape_vec((height, width), arr?)?;
-        Ok(Map { width, height, arr })
+        let grid = Grid::parse_with(input, |x| {
+            x.to_digit(10)
+                .map(|d| d as u8)
+                .ok_or(anyhow!("Failed to parse {}", x))
+        })?;
+        Ok(Map { grid })
     }
 
     fn get_trailheads(&self) -> Result<Vec<Node>> {
-        let mut res = vec![];
-        for y in 0..self.arr.dim().0 {
-            for x in 0..self.arr.dim().1 {
-                if let Some(&TRAILHEAD) = self.arr.get((y, x)) {
-                    res.push(Node {
-                        x,
-                        y,
-                        val: 0,
-                        map: &self,
-                    });
-                }
-            }
-        }
+        let res = self
+            .grid
+            .find_all(&TRAILHEAD)
+            .map(|(x, y)| Node {
+                x,
+                y,
+                val: TRAILHEAD,
+                map: self,
+            })
+            .collect();
         Ok(res)
     }
 }
@@ -133,7 +130,11 @@ pub fn part_one(input: &str) -> Option<usize> {
 pub fn part_two(input: &str) -> Option<usize> {
     let map = Map::parse(input).unwrap();
     let trailheads = map.get_trailheads().unwrap();
-    let reached: usize = trailheads.into_iter().map(|t| t.explore_all()).sum();
+    let mut memo = Memo::new();
+    let reached: usize = trailheads
+        .into_iter()
+        .map(|t| t.explore_all(&mut memo))
+        .sum();
     Some(reached)
 }
 
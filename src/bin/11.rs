@@ -1,6 +1,7 @@
 use std::fmt::Display;
 use std::mem;
 
+use advent_of_code::{memo::Memo, parse};
 use anyhow::{anyhow, Context, Result};
 
 advent_of_code::solution!(11);
@@ -86,8 +87,33 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(head.count())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+/*
+ * Stones never interact, and many end up with the same value : the number of stones a value
+ * turns into after some blinks is computed once per value and number of blinks
+ */
+fn count_stones(memo: &mut Memo<(u64, u32), u64>, value: u64, blinks: u32) -> u64 {
+    if blinks == 0 {
+        return 1;
+    }
+    memo.get((value, blinks), |memo, _| {
+        if value == 0 {
+            return count_stones(memo, 1, blinks - 1);
+        }
+        let count = value.ilog10() + 1;
+        if count.is_multiple_of(2) {
+            let mask = 10u64.pow(count >> 1);
+            count_stones(memo, value / mask, blinks - 1)
+                + count_stones(memo, value % mask, blinks - 1)
+        } else {
+            count_stones(memo, value * 2024, blinks - 1)
+        }
+    })
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let stones = parse::unsigned::<u64>(input).unwrap();
+    let mut memo = Memo::new();
+    Some(stones.iter().map(|s| count_stones(&mut memo, *s, 75)).sum())
 }
 
 #[cfg(test)]
@@ -103,6 +129,6 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(55312));
+        assert_eq!(result, Some(65601038650482));
     }
 }
@@ -1,83 +1,24 @@
-use anyhow::{anyhow, Result};
-use ndarray::{Array, Array2};
-use std::{
-    collections::{HashSet, VecDeque},
-    hash::Hash,
-    ops::Add,
+use advent_of_code::{
+    geometry::{Direction, Position},
+    grid::Grid,
 };
-use strum::{EnumIter, IntoEnumIterator};
+use anyhow::{anyhow, Result};
+use std::collections::{HashSet, VecDeque};
 
 advent_of_code::solution!(12);
 
 const EXPLORED: char = '*';
 
-fn parse(input: &str) -> Result<Array2<char>> {
-    let width = input
-        .chars()
-        .fold((0, true), |acc, elem| {
-            if acc.1 {
-                let valid = elem != '\n';
-                return (acc.0 + (valid as usize), valid);
-            }
-            acc
-        })
-        .0;
-    let height = input.lines().count();
-    let chars: Vec<char> = input.chars().filter(|x| *x != '\n').collect();
-    Array::from_shape_vec((height, width), chars).map_err(|err| {
-        anyhow!(
-            "Failed to build array from dims {}x{}: {}",
-            height,
-            width,
-            err
-        )
-    })
-}
-
-#[derive(Clone, Copy, Debug, EnumIter, Eq, Hash, PartialEq)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq)]
-struct Position {
-    x: usize,
-    y: usize,
-}
-
-impl Add<&Direction> for &Position {
-    type Output = Option<Position>;
-    fn add(self, rhs: &Direction) -> Self::Output {
-        match rhs {
-            Direction::Up => Some(Position {
-                x: self.x,
-                y: self.y.checked_sub(1)?,
-            }),
-            Direction::Right => Some(Position {
-                x: self.x.checked_add(1)?,
-                y: self.y,
-            }),
-            Direction::Down => Some(Position {
-                x: self.x,
-                y: self.y.checked_add(1)?,
-            }),
-            Direction::Left => Some(Position {
-                x: self.x.checked_sub(1)?,
-                y: self.y,
-            }),
-        }
-    }
+fn parse(input: &str) -> Result<Grid<char>> {
+    Ok(Grid::parse(input)?)
 }
 
 /*
  * Explore and compute area related to starting position <pos>.
  */
 fn explore(
-    arr: &mut Array2<char>,
-    arr_original: &Array2<char>,
+    arr: &mut Grid<char>,
+    arr_original: &Grid<char>,
     expl_stack: &mut VecDeque<Position>,
     pos: Position,
     c: char,
@@ -86,11 +27,12 @@ fn explore(
     let mut perimeter = 0;
     let mut siblings_expl_stack = VecDeque::from([pos]);
     let mut arr_clone = arr_original.clone();
+    let size = arr_original.size();
 
     while let Some(pos) = siblings_expl_stack.pop_front() {
         if let (Some(plot), Some(plot_clone)) = (
-            arr.get_mut((pos.y, pos.x)),
-            arr_clone.get_mut((pos.y, pos.x)),
+            arr.get_mut((pos.x, pos.y)),
+            arr_clone.get_mut((pos.x, pos.y)),
         ) {
             // If this position hash already been explored for this character, skip
             if *plot_clone == EXPLORED {
@@ -99,8 +41,7 @@ fn explore(
             // If this plot is a sibling
             else if *plot_clone == c {
                 area += 1;
-                let neighbors: Vec<Position> =
-                    Direction::iter().filter_map(|dir| &pos + &dir).collect();
+                let neighbors: Vec<Position> = pos.neighbours(size).collect();
                 perimeter += (4 - neighbors.len()) as u64; // Dont forget to add fences outside bounds
                 siblings_expl_stack.extend(neighbors); // Explore neighbors
                 *plot = EXPLORED; // Mark it as explored
@@ -127,13 +68,13 @@ fn explore(
 /*
  * Compute total cost of a region
  */
-fn compute_price(mut arr: Array2<char>) -> u64 {
+fn compute_price(mut arr: Grid<char>) -> u64 {
     let mut res = 0;
     let arr_clone = arr.clone();
     let mut expl_stack = VecDeque::from([Position { x: 0, y: 0 }]);
 
     while let Some(pos) = expl_stack.pop_front() {
-        if let Some(&c) = arr.get((pos.y, pos.x)) {
+        if let Some(&c) = arr.get((pos.x, pos.y)) {
             // If never explored, start a new exploration
             if c != EXPLORED {
                 res += explore(&mut arr, &arr_clone, &mut expl_stack, pos, c);
@@ -170,11 +111,13 @@ impl Plant {
     /*
      * Return uncharted neighbors of plant in 2 categories : siblings or fence
      */
-    fn neighbors(&self, arr: &Array2<char>, c: char) -> Vec<Neighbor> {
-        Direction::iter()
+    fn neighbors(&self, arr: &Grid<char>, c: char) -> Vec<Neighbor> {
+        let size = arr.size();
+        Direction::ALL
+            .into_iter()
             .filter_map(|dir| {
-                if let Some(pos) = &self.pos + &dir {
-                    if let Some(&cn) = arr.get((pos.y, pos.x)) {
+                if let Some(pos) = self.pos.step(dir, size) {
+                    if let Some(&cn) = arr.get((pos.x, pos.y)) {
                         if c == cn {
                             return Some(Neighbor::Sibling(pos));
                         }
@@ -194,20 +137,19 @@ impl Plant {
     }
 }
 
-
 /*
  * Explore and compute area related to starting position <pos>.
  */
 fn explore_discount(
-    arr: &mut Array2<char>,
-    original_arr: &Array2<char>,
+    arr: &mut Grid<char>,
+    original_arr: &Grid<char>,
     pos: Position,
     queue: &mut VecDeque<Position>,
     c: char,
 ) -> Result<u64> {
     let mut area = 0;
     let mut perimeter = 0;
-    // Array to be edited during exploration
+    // Grid to be edited during exploration
     let mut local_arr = original_arr.clone();
     let plant = Plant {
         pos,
@@ -230,7 +172,7 @@ fn explore_discount(
                 }
                 // If uncharted yet, add it to queue
                 if let Some(pos) = fence.pos {
-                    if let Some(&c) = arr.get((pos.y, pos.x)) {
+                    if let Some(&c) = arr.get((pos.x, pos.y)) {
                         if c != EXPLORED {
                             if !queue.contains(&pos) {
                                 queue.push_back(pos);
@@ -272,14 +214,14 @@ fn explore_discount(
 
         // Update arrays
         let c = local_arr
-            .get_mut((plant.pos.y, plant.pos.x))
+            .get_mut((plant.pos.x, plant.pos.y))
             .ok_or(anyhow!(
                 "Failed to update plant type at ({},{})",
                 plant.pos.x,
                 plant.pos.y
             ))?;
         *c = EXPLORED;
-        let c = arr.get_mut((plant.pos.y, plant.pos.x)).unwrap();
+        let c = arr.get_mut((plant.pos.x, plant.pos.y)).unwrap();
         *c = EXPLORED;
 
         area += 1;
@@ -291,14 +233,14 @@ fn explore_discount(
 /*
  * Compute total price with discount applied
  */
-fn compute_price_with_discount(mut arr: Array2<char>) -> Result<u64> {
+fn compute_price_with_discount(mut arr: Grid<char>) -> Result<u64> {
     let original_arr = arr.clone();
     let mut cost = 0;
     let mut queue = VecDeque::from([Position { x: 0, y: 0 }]);
 
     while let Some(pos) = queue.pop_front() {
         // This position might have been explored meanwhile
-        let c = *arr.get((pos.y, pos.x)).ok_or(anyhow!(
+        let c = *arr.get((pos.x, pos.y)).ok_or(anyhow!(
             "Failed to read array at ({},{})",
             pos.x,
             pos.y
@@ -336,4 +278,4 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(1206));
     }
-}
\ No newline at end of file
+}
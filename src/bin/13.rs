@@ -1,9 +1,12 @@
-use anyhow::{anyhow, Error, Result};
-use ndarray::{arr2, Array};
-use regex::Regex;
-use std::iter::zip;
+use advent_of_code::{
+    math::{self, Solutions},
+    parse,
+};
+use anyhow::Result;
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(13, variants {
+    part_one => [part_one_brute_force],
+});
 
 const A_COST: u64 = 3;
 const B_COST: u64 = 1;
@@ -14,21 +17,6 @@ struct Offset {
     y: u64,
 }
 
-impl TryFrom<(&str, &Regex)> for Offset {
-    type Error = Error;
-    fn try_from(value: (&str, &Regex)) -> std::result::Result<Self, Self::Error> {
-        let line = value.0;
-        let re = value.1;
-        let err = |l: &str| anyhow!("Failed to parse {}", l);
-        let capture = re.captures(line).ok_or(err(line))?;
-        let x = capture.get(1).ok_or(err(line))?.as_str();
-        let x: u64 = x.parse().map_err(|_| err(x))?;
-        let y = capture.get(2).ok_or(err(line))?.as_str();
-        let y: u64 = y.parse().map_err(|_| err(y))?;
-        Ok(Offset { x, y })
-    }
-}
-
 #[derive(Debug)]
 struct Machine {
     a: Offset,
@@ -38,81 +26,74 @@ struct Machine {
 
 impl Machine {
     fn parse(input: &str) -> Result<Vec<Machine>> {
-        let mut res = vec![];
-        let iter = zip(input.lines().step_by(4), input.lines().skip(1).step_by(4));
-        let iter = zip(iter, input.lines().skip(2).step_by(4));
-        let re_a = Regex::new(r"Button A: X\+(\d+), Y\+(\d+)")?;
-        let re_b = Regex::new(r"Button B: X\+(\d+), Y\+(\d+)")?;
-        let re_prize = Regex::new(r"Prize: X=(\d+), Y=(\d+)")?;
-        for ((a, b), prize) in iter {
-            res.push(Machine {
-                a: (a, &re_a).try_into()?,
-                b: (b, &re_b).try_into()?,
-                prize: (prize, &re_prize).try_into()?,
-            });
-        }
-        Ok(res)
+        let machines = parse::each_block(input, |block| {
+            let [ax, ay, bx, by, px, py] = parse::unsigned_n(block)?;
+            Ok(Machine {
+                a: Offset { x: ax, y: ay },
+                b: Offset { x: bx, y: by },
+                prize: Offset { x: px, y: py },
+            })
+        })?;
+        Ok(machines)
     }
 
     /*
-     * Use Gauss pivot to solve linear system
+     * Solve the linear system exactly, since part two's offsets overflow intermediate products.
+     * With collinear buttons, any number of presses may work : take the cheapest
      */
     fn solve(&self) -> Option<u64> {
-        let mut system = arr2(&[
-            [self.a.x, self.b.x, self.prize.x],
-            [self.a.y, self.b.y, self.prize.y],
-        ])
-        .map(|x| *x as i64);
-
-        // Eliminate bottom left term
-        let c0 = -*system.get((1, 0)).unwrap();
-        let c1 = *system.get((0, 0)).unwrap();
-        for j in 0..system.dim().1 {
-            let r0 = *system.get((0, j)).unwrap();
-            let r1 = system.get_mut((1, j)).unwrap();
-            *r1 = c0 * r0 + c1 * *r1;
-        }
-
-        // Attempt to normalize last row
-        let q = *system.get((1, 1)).unwrap();
-        let p = system.get_mut((1, 2)).unwrap();
-        if p.checked_rem(q)? != 0 {
-            return None;
-        }
-        *p = *p / q;
-        *system.get_mut((1, 1)).unwrap() = 1;
-
-        // Eliminate top right term
-        let c0 = 1;
-        let c1 = -*system.get((0, 1)).unwrap();
-        for j in 0..system.dim().1 {
-            let r1 = *system.get((1, j)).unwrap();
-            let r0 = system.get_mut((0, j)).unwrap();
-            *r0 = c0 * *r0 + c1 * r1;
-        }
-
-        // Attempt to normalize first row
-        let q = *system.get((0, 0)).unwrap();
-        let p = system.get_mut((0, 2)).unwrap();
-        if p.checked_rem(q)? != 0 {
+        let [ax, ay, bx, by, px, py] = [
+            self.a.x,
+            self.a.y,
+            self.b.x,
+            self.b.y,
+            self.prize.x,
+            self.prize.y,
+        ]
+        .map(i128::from);
+        let (a, b) = match math::solve(&[vec![ax, bx], vec![ay, by]], &[px, py]) {
+            Solutions::None => return None,
+            Solutions::One(presses) => (presses[0].to_integer()?, presses[1].to_integer()?),
+            Solutions::Infinite { .. } => {
+                // every equation is a multiple of a non-zero one
+                let (a, b, p) = if (ax, bx) != (0, 0) {
+                    (ax, bx, px)
+                } else {
+                    (ay, by, py)
+                };
+                math::min_cost_combination(a, b, p, A_COST.into(), B_COST.into())?
+            }
+        };
+        if a < 0 || b < 0 {
             return None;
         }
-        *p = *p / q;
-        *system.get_mut((0, 0)).unwrap() = 1;
 
-        // Try to convert
-        let a = *system.get((0, 2)).unwrap();
-        let b = *system.get((1, 2)).unwrap();
-        if (a < 0) | (b < 0) {
-            return None;
-        }
-        let a = a as u64;
-        let b = b as u64;
-
-        Some(a * A_COST + b * B_COST)
+        Some(a as u64 * A_COST + b as u64 * B_COST)
     }
 }
 
+/*
+ * Reference implementation : try every number of presses, each button
+ * being pressed at most 100 times in part one
+ */
+pub fn part_one_brute_force(input: &str) -> Option<u64> {
+    let machines = Machine::parse(input).unwrap();
+    Some(
+        machines
+            .iter()
+            .filter_map(|m| {
+                (0..=100u64)
+                    .flat_map(|a| (0..=100u64).map(move |b| (a, b)))
+                    .filter(|(a, b)| {
+                        a * m.a.x + b * m.b.x == m.prize.x && a * m.a.y + b * m.b.y == m.prize.y
+                    })
+                    .map(|(a, b)| a * A_COST + b * B_COST)
+                    .min()
+            })
+            .sum(),
+    )
+}
+
 pub fn part_one(input: &str) -> Option<u64> {
     let machines = Machine::parse(input).unwrap();
     Some(machines.iter().fold(0, |acc, m| match m.solve() {
@@ -136,6 +117,10 @@ pub fn part_two(input: &str) -> Option<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::{
+        differential::{check, no_shrink, ListOf},
+        rng::Rng,
+    };
 
     #[test]
     fn test_part_one() {
@@ -143,9 +128,69 @@ mod tests {
         assert_eq!(result, Some(480));
     }
 
+    #[test]
+    fn test_part_one_brute_force() {
+        let result = part_one_brute_force(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(480));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(875318608908)); // Had to compute it on my own
     }
+
+    /*
+     * Random machines with non-collinear buttons (with collinear ones, the cheapest solution may
+     * need more than the 100 presses the brute force tries).
+     * The prize is either reached with at most 100 presses of each button, or unreachable
+     * because its X coordinate is odd while every button moves by an even amount
+     */
+    fn machines() -> ListOf<[u64; 6]> {
+        ListOf {
+            len: 1..=8,
+            separator: "\n\n",
+            item: |rng: &mut Rng| loop {
+                let even = rng.chance(0.3);
+                let mut offset = || {
+                    let v = rng.range(1..=99);
+                    if even {
+                        v & !1
+                    } else {
+                        v
+                    }
+                };
+                let [ax, ay, bx, by] = [offset(), offset(), offset(), offset()];
+                if [ax, ay, bx, by].contains(&0) || ax * by == ay * bx {
+                    continue;
+                }
+                let (a, b) = (rng.range(0..=100), rng.range(0..=100));
+                let (mut px, py) = (a * ax + b * bx, a * ay + b * by);
+                if even {
+                    px += 1;
+                }
+                break [ax, ay, bx, by, px, py];
+            },
+            render: |[ax, ay, bx, by, px, py]| {
+                format!("Button A: X+{ax}, Y+{ay}\nButton B: X+{bx}, Y+{by}\nPrize: X={px}, Y={py}")
+            },
+            shrink: no_shrink,
+        }
+    }
+
+    #[test]
+    fn test_part_one_differential() {
+        check(
+            DAY,
+            1,
+            &machines(),
+            &[
+                (
+                    "part_one_brute_force",
+                    part_one_brute_force as fn(&str) -> _,
+                ),
+                ("part_one", part_one),
+            ],
+        );
+    }
 }
@@ -1,8 +1,11 @@
 use std::fmt::Display;
 
+use advent_of_code::{
+    geometry::{Offset, Position},
+    math, parse,
+};
 use anyhow::{anyhow, Result};
 use ndarray::Array2;
-use regex::Regex;
 
 advent_of_code::solution!(14);
 
@@ -29,33 +32,19 @@ struct TileMap {
 
 impl Robot {
     fn parse(line: &str) -> Result<Self> {
-        let re = Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)")?;
-        let err = |l: &str| anyhow!("Failed to parse {}", l);
-        let capture = re.captures(line).ok_or(err(line))?;
-        let px = capture.get(1).ok_or(err(line))?.as_str();
-        let py = capture.get(2).ok_or(err(line))?.as_str();
-        let vx = capture.get(3).ok_or(err(line))?.as_str();
-        let vy = capture.get(4).ok_or(err(line))?.as_str();
+        let [px, py, vx, vy] = parse::signed_n::<isize, 4>(line)?;
         Ok(Robot {
-            px: px.parse().map_err(|_| err(px))?,
-            py: py.parse().map_err(|_| err(py))?,
-            vx: vx.parse().map_err(|_| err(vx))?,
-            vy: vy.parse().map_err(|_| err(vy))?,
+            px: usize::try_from(px)?,
+            py: usize::try_from(py)?,
+            vx,
+            vy,
         })
     }
 
     fn step(&mut self, width: usize, height: usize) {
-        // Quick and dirty hack : assume that displacement < boundaries
-        self.px = self
-            .px
-            .checked_add_signed(width as isize + self.vx)
-            .unwrap()
-            % width;
-        self.py = self
-            .py
-            .checked_add_signed(height as isize + self.vy)
-            .unwrap()
-            % height;
+        let pos = Position::new(self.px, self.py)
+            .wrapping_step(Offset::new(self.vx, self.vy), (width, height));
+        (self.px, self.py) = pos.into();
     }
 }
 
@@ -148,8 +137,11 @@ pub fn part_two(input: &str) -> Option<u32> {
     // Since the picture is assumed to be concentrated in one point,
     // it should have a small safety factor !
     let mut min_safety_factor = tilemap.safety_factor();
+    // Every robot is back where it started after lcm(width, height) steps,
+    // so there is no point looking further
+    let period = math::lcm(tilemap.width, tilemap.height);
     let mut min_index = 0;
-    for i in 1..20000 {
+    for i in 1..period {
         tilemap.step();
         let safety_factor = tilemap.safety_factor();
         if safety_factor < min_safety_factor {
@@ -159,7 +151,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
 
-    Some(min_index)
+    Some(min_index as u32)
 }
 
 #[cfg(test)]
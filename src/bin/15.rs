@@ -1,7 +1,11 @@
-use std::{collections::VecDeque, fmt::Display, ops::AddAssign};
+use std::{collections::VecDeque, fmt::Display, mem};
 
+use advent_of_code::{
+    geometry::{Direction, Position},
+    grid::Grid,
+    parse,
+};
 use anyhow::{anyhow, Error, Result};
-use ndarray::Array2;
 
 advent_of_code::solution!(15);
 
@@ -36,73 +40,16 @@ impl TryFrom<char> for Cell {
     }
 }
 
-#[derive(Debug)]
-enum Instruction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-impl Display for Instruction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Instruction::Up => write!(f, "^"),
-            Instruction::Right => write!(f, ">"),
-            Instruction::Down => write!(f, "v"),
-            Instruction::Left => write!(f, "<"),
-        }
-    }
-}
-
-impl TryFrom<char> for Instruction {
-    type Error = Error;
-    fn try_from(value: char) -> std::result::Result<Self, Self::Error> {
-        match value {
-            '^' => Ok(Self::Up),
-            '>' => Ok(Self::Right),
-            'v' => Ok(Self::Down),
-            '<' => Ok(Self::Left),
-            _ => Err(anyhow!("Failed to parse instruction \"{}\"", value)),
-        }
-    }
-}
-
-#[derive(Clone)]
-struct Position {
-    x: usize,
-    y: usize,
-}
-
-/*
- * No checks are done on this function, use cautiously !
- */
-impl AddAssign<&Instruction> for Position {
-    fn add_assign(&mut self, instr: &Instruction) {
-        match instr {
-            Instruction::Up => self.y -= 1,
-            Instruction::Right => self.x += 1,
-            Instruction::Down => self.y += 1,
-            Instruction::Left => self.x -= 1,
-        }
-    }
-}
-
 struct Warehouse {
-    arr: Array2<Cell>,
-    instructions: VecDeque<Instruction>,
+    arr: Grid<Cell>,
+    instructions: VecDeque<Direction>,
     robot: Position,
 }
 
 impl Display for Warehouse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // First display the array
-        for row in self.arr.rows() {
-            for cell in row {
-                write!(f, "{}", cell)?;
-            }
-            writeln!(f, "")?;
-        }
+        // First display the grid
+        write!(f, "{}", self.arr)?;
 
         // Then display instructions
         for instr in &self.instructions {
@@ -115,50 +62,24 @@ impl Display for Warehouse {
 
 impl Warehouse {
     fn parse(input: &str) -> Result<Self> {
-        let height = input
-            .lines()
-            .count()
-            .checked_sub(2)
-            .ok_or(anyhow!("Not enough lines"))?;
-        let width = input.lines().next().unwrap().len();
-        let lines = input.lines();
-
-        let arr = lines
-            .clone()
-            .take(height)
-            .map(|l| {
-                Ok(l.chars()
-                    .map(|c| {
-                        let cell: Cell = c.try_into()?;
-                        Ok(cell)
-                    })
-                    .collect::<Result<Vec<Cell>>>()?)
-            })
-            .collect::<Result<Vec<Vec<Cell>>>>()?
-            .into_iter()
-            .flatten()
-            .collect();
-        let arr = Array2::from_shape_vec((height, width), arr)?;
+        let mut blocks = parse::blocks(input);
+        let map = blocks.next().ok_or(anyhow!("Not enough lines"))?;
+        let arr = Grid::<Cell>::parse(map)?;
 
-        let instructions = lines
-            .skip(height + 1)
+        // Instructions may be wrapped over several lines
+        let instructions = blocks
             .next()
-            .unwrap()
-            .chars()
+            .ok_or(anyhow!("No instructions"))?
+            .lines()
+            .flat_map(str::chars)
             .map(|c| Ok(c.try_into()?))
-            .collect::<Result<VecDeque<Instruction>>>()?;
+            .collect::<Result<VecDeque<Direction>>>()?;
 
-        let robot_pos = (|| {
-            for y in 0..arr.dim().0 {
-                for x in 0..arr.dim().1 {
-                    let cell = arr.get((y, x)).unwrap();
-                    if let Cell::Robot = cell {
-                        return Ok(Position { x, y });
-                    }
-                }
-            }
-            Err(anyhow!("No robot found in initial map"))
-        })()?;
+        let robot_pos = arr
+            .iter()
+            .find_map(|(pos, cell)| matches!(cell, Cell::Robot).then_some(pos))
+            .ok_or(anyhow!("No robot found in initial map"))?
+            .into();
 
         Ok(Self {
             arr,
@@ -170,40 +91,15 @@ impl Warehouse {
     /*
      * Returns an Option to a position representing a neighbor, if it exists.
      */
-    fn get_neighbor(&self, pos: &Position, instr: &Instruction) -> Option<Position> {
-        match instr {
-            Instruction::Up => pos
-                .y
-                .checked_sub(1)
-                .and_then(|new_y| Some(Position { x: pos.x, y: new_y })),
-            Instruction::Right => {
-                let new_x = pos.x + 1;
-                if new_x < self.arr.dim().1 {
-                    Some(Position { x: new_x, y: pos.y })
-                } else {
-                    None
-                }
-            }
-            Instruction::Down => {
-                let new_y = pos.y + 1;
-                if new_y < self.arr.dim().0 {
-                    Some(Position { x: pos.x, y: new_y })
-                } else {
-                    None
-                }
-            }
-            Instruction::Left => pos
-                .x
-                .checked_sub(1)
-                .and_then(|new_x| Some(Position { x: new_x, y: pos.y })),
-        }
+    fn get_neighbor(&self, pos: &Position, instr: &Direction) -> Option<Position> {
+        pos.step(*instr, self.arr.size())
     }
 
     /*
      * Returns <true> if this cell was able to move
      */
-    fn step_cell(&mut self, pos: &Position, instr: &Instruction) -> Result<bool> {
-        match self.arr.get((pos.y, pos.x)).ok_or(anyhow!(
+    fn step_cell(&mut self, pos: &Position, instr: &Direction) -> Result<bool> {
+        match self.arr.get((*pos).into()).ok_or(anyhow!(
             "Failed to get cell at ({},{})",
             pos.x,
             pos.y
@@ -214,10 +110,8 @@ impl Warehouse {
                 let neighbor = self.get_neighbor(pos, instr);
                 if let Some(neighbor) = neighbor {
                     if self.step_cell(&neighbor, instr)? {
-                        // Swap and replace current by empty
-                        self.arr.swap((pos.y, pos.x), (neighbor.y, neighbor.x));
-                        let current = self.arr.get_mut((pos.y, pos.x)).unwrap();
-                        *current = Cell::Empty;
+                        // Move to the neighbor and replace current by empty
+                        self.arr[neighbor] = mem::replace(&mut self.arr[*pos], Cell::Empty);
                         return Ok(true);
                     }
                 }
@@ -235,7 +129,9 @@ impl Warehouse {
         if let Some(instr) = self.instructions.pop_front() {
             // Move robot position only iff the cell was effectively moved
             if self.step_cell(&self.robot.clone(), &instr)? {
-                self.robot += &instr;
+                self.robot = self
+                    .get_neighbor(&self.robot, &instr)
+                    .ok_or(anyhow!("Robot moved out of the map"))?;
             }
             return Ok(true);
         }
@@ -243,15 +139,11 @@ impl Warehouse {
     }
 
     fn coordinates(&self) -> u64 {
-        let mut res = 0;
-        for y in 0..self.arr.dim().0 {
-            for x in 0..self.arr.dim().1 {
-                if let Some(Cell::Box) = self.arr.get((y, x)) {
-                    res += (100 * y + x) as u64;
-                }
-            }
-        }
-        res
+        self.arr
+            .iter()
+            .filter(|(_, cell)| matches!(cell, Cell::Box))
+            .map(|((x, y), _)| (100 * y + x) as u64)
+            .sum()
     }
 }
 
@@ -278,6 +170,15 @@ mod tests {
         assert_eq!(result, Some(10092));
     }
 
+    #[test]
+    fn test_part_one_wrapped_moves() {
+        // The moves of this example are wrapped over two lines
+        let result = part_one(&advent_of_code::template::read_file_part(
+            "examples", DAY, 2,
+        ));
+        assert_eq!(result, Some(2028));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
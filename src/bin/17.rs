@@ -1,10 +1,7 @@
-use std::{
-    fmt::{Display, Formatter},
-    iter::zip,
-};
+use std::fmt::{Display, Formatter};
 
+use advent_of_code::parse;
 use anyhow::{anyhow, Error, Result};
-use regex::Regex;
 
 advent_of_code::solution!(17);
 
@@ -131,49 +128,29 @@ impl Display for Program {
 
 impl Program {
     fn parse<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Result<Self> {
-        let mut instrs = vec![];
         let l = lines
             .next()
             .ok_or(anyhow!("Not enough lines in input file !"))?;
-        let instrs_str = Regex::new(r"Program: ((\d\,)+\d)")?
-            .captures(l)
-            .and_then(|m| Some(m.get(1)?.as_str()))
-            .ok_or(anyhow!("Failed to parse line {}", l))?;
+        let values = parse::field(l, "Program", parse::list::<u64>)?;
 
-        for (opcode, operand) in zip(
-            instrs_str.chars().step_by(2),
-            instrs_str.chars().step_by(2).skip(1),
-        ) {
-            // Assuming this has worked thanks to previous regex
-            let opcode = opcode.to_digit(10).unwrap() as u64;
-            let operand = operand.to_digit(10).unwrap() as u64;
-            instrs.push((opcode, operand).try_into()?);
-        }
+        // One instruction per offset, so that jumps can land on any of them
+        let instrs = values
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]).try_into())
+            .collect::<Result<Vec<Opcode>>>()?;
 
         Ok(Self { instrs })
     }
 
     fn parse2<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Result<(String, Self)> {
-        let mut instrs = vec![];
-        let l = lines
-            .next()
-            .ok_or(anyhow!("Not enough lines in input file !"))?;
-        let instrs_str = Regex::new(r"Program: ((\d\,)+\d)")?
-            .captures(l)
-            .and_then(|m| Some(m.get(1)?.as_str()))
-            .ok_or(anyhow!("Failed to parse line {}", l))?;
-
-        for (opcode, operand) in zip(
-            instrs_str.chars().step_by(2),
-            instrs_str.chars().step_by(2).skip(1),
-        ) {
-            // Assuming this has worked thanks to previous regex
-            let opcode = opcode.to_digit(10).unwrap() as u64;
-            let operand = operand.to_digit(10).unwrap() as u64;
-            instrs.push((opcode, operand).try_into()?);
-        }
-
-        Ok((instrs_str.to_owned(), Self { instrs }))
+        let mut peekable = lines.peekable();
+        let instrs_str = peekable
+            .peek()
+            .map(|l| parse::key_value(l))
+            .transpose()?
+            .map(|(_, value)| value.to_owned())
+            .unwrap_or_default();
+        Ok((instrs_str, Self::parse(&mut peekable)?))
     }
 }
 
@@ -187,31 +164,17 @@ struct Machine {
 
 impl Machine {
     fn parse<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Result<Self> {
-        let err = || anyhow!("Not enough lines in input file !");
-        let err2 = |l| anyhow!("Failed to parse line {}", l);
-        let l = lines.next().ok_or(err())?;
-        let reg_a = Regex::new(r"Register A: (\d+)")?
-            .captures(l)
-            .and_then(|m| Some(m.get(1)?.as_str()))
-            .ok_or(err2(l))?
-            .parse()?;
-        let l = lines.next().ok_or(err())?;
-        let reg_b = Regex::new(r"Register B: (\d+)")?
-            .captures(l)
-            .and_then(|m| Some(m.get(1)?.as_str()))
-            .ok_or(err2(l))?
-            .parse()?;
-        let l = lines.next().ok_or(err())?;
-        let reg_c = Regex::new(r"Register C: (\d+)")?
-            .captures(l)
-            .and_then(|m| Some(m.get(1)?.as_str()))
-            .ok_or(err2(l))?
-            .parse()?;
+        let mut register = |name| -> Result<u64> {
+            let l = lines
+                .next()
+                .ok_or(anyhow!("Not enough lines in input file !"))?;
+            Ok(parse::field(l, name, parse::number)?)
+        };
         Ok(Self {
             ip: 0,
-            reg_a,
-            reg_b,
-            reg_c,
+            reg_a: register("Register A")?,
+            reg_b: register("Register B")?,
+            reg_c: register("Register C")?,
         })
     }
 
@@ -250,11 +213,10 @@ impl Machine {
 }
 
 pub fn part_one(input: &str) -> Option<String> {
-    let mut lines = input.lines();
-    let mut machine = Machine::parse(&mut lines).unwrap();
+    let mut blocks = parse::blocks(input);
+    let mut machine = Machine::parse(&mut blocks.next()?.lines()).unwrap();
     println!("{:?}", machine);
-    lines.next();
-    let program = Program::parse(&mut lines).unwrap();
+    let program = Program::parse(&mut blocks.next()?.lines()).unwrap();
     println!("{}", program);
 
     let res = machine.run(&program).unwrap();
@@ -264,11 +226,10 @@ pub fn part_one(input: &str) -> Option<String> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut lines = input.lines();
-    let machine = Machine::parse(&mut lines).unwrap();
+    let mut blocks = parse::blocks(input);
+    let machine = Machine::parse(&mut blocks.next()?.lines()).unwrap();
     println!("{:?}", machine);
-    lines.next();
-    let (program_string, program) = Program::parse2(&mut lines).unwrap();
+    let (program_string, program) = Program::parse2(&mut blocks.next()?.lines()).unwrap();
     println!("{}", program);
 
     // let mut start_reg_a = 64_119_171_111_111;
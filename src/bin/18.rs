@@ -1,309 +1,149 @@
-use std::{collections::HashSet, hash::Hash};
+use std::collections::HashSet;
 
-use anyhow::{anyhow, Result};
-use pathfinding::{
-    directed::bfs,
-    prelude::{bfs, dfs, dijkstra},
+use advent_of_code::{
+    geometry::Position,
+    parse::{self, ParseError},
+    search,
+    union_find::{GridNodes, Timeline},
 };
-use regex::Regex;
+use anyhow::{anyhow, Result};
+
+advent_of_code::solution!(18, scale = scale_input);
+
+const SIZE: usize = 71;
+const FALLEN: usize = 1024;
 
-advent_of_code::solution!(18);
+/*
+ * The grid size is fixed, so bytes are repeated instead of adding new ones. The bytes fallen for
+ * part one are kept as they are, so its walls and shortest path, and thus its work, stay the same ;
+ * only the bytes after them are repeated, which part two has to go through
+ */
+fn scale_input(input: &str, factor: usize) -> String {
+    repeat_after(input, factor, FALLEN)
+}
 
-#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
-struct Position {
-    x: usize,
-    y: usize,
+fn repeat_after(input: &str, factor: usize, fallen: usize) -> String {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(i, line)| std::iter::repeat_n(line, if i < fallen { 1 } else { factor }))
+        .map(|line| format!("{line}\n"))
+        .collect()
 }
 
-enum Dir {
-    Up,
-    Right,
-    Down,
-    Left,
+fn parse_position(line: &str) -> Result<Position, ParseError> {
+    let [x, y] = parse::unsigned_n(line)?;
+    Ok(Position { x, y })
 }
 
 #[derive(Debug)]
 struct Map {
-    walls: HashSet<Position>,
-    width: usize,
-    height: usize,
+    bytes: Vec<Position>,
+    size: usize,
 }
 
 impl Map {
-    fn parse(input: &str, width: usize, height: usize, fallen: usize) -> Result<Self> {
-        let mut walls = HashSet::<Position>::new();
-        let re = Regex::new(r"(\d+),(\d+)$")?;
-        for l in input.lines().take(fallen) {
-            let captures = re.captures(l).ok_or(anyhow!("Failed to parse position"))?;
-            let x = captures
-                .get(1)
-                .and_then(|x| x.as_str().parse().ok())
-                .ok_or(anyhow!("Failed to parse x"))?;
-            let y = captures
-                .get(2)
-                .and_then(|y| y.as_str().parse().ok())
-                .ok_or(anyhow!("Failed to parse y"))?;
-            walls.insert(Position { x, y });
+    fn parse(input: &str, size: usize) -> Result<Self> {
+        let bytes = parse::each_line(input, parse_position)?;
+        if let Some(p) = bytes.iter().find(|p| p.x >= size || p.y >= size) {
+            return Err(anyhow!("Byte {} falls outside of the grid", p));
         }
-        Ok(Self {
-            walls,
-            width,
-            height,
-        })
+        Ok(Self { bytes, size })
     }
-}
 
-#[derive(Debug, Clone, Copy)]
-struct Cell<'a> {
-    pos: Position,
-    map: &'a Map,
-}
-
-impl<'a> Hash for Cell<'a> {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.pos.hash(state);
+    fn start(&self) -> Position {
+        Position::new(0, 0)
     }
-}
 
-impl<'a> PartialEq for Cell<'a> {
-    fn eq(&self, other: &Self) -> bool {
-        self.pos == other.pos
+    fn exit(&self) -> Position {
+        Position::new(self.size - 1, self.size - 1)
     }
 }
 
-impl<'a> Eq for Cell<'a> {}
+/*
+ * The shortest path to the exit on a grid of the given size, once the first bytes have fallen
+ */
+fn shortest_path(input: &str, size: usize, fallen: usize) -> Option<usize> {
+    let map = Map::parse(input, size).unwrap();
+    let size = (map.size, map.size);
+    let walls: HashSet<Position> = map.bytes.iter().take(fallen).copied().collect();
 
-impl<'a> Cell<'a> {
-    fn get_neighbor(&self, dir: &Dir) -> Option<Self> {
-        let neighbor = Cell {
-            map: self.map,
-            pos: match dir {
-                Dir::Up => Position {
-                    x: self.pos.x,
-                    y: self.pos.y.checked_sub(1)?,
-                },
-                Dir::Right => {
-                    let x = self.pos.x.checked_add(1)?;
-                    if x >= self.map.width {
-                        None?
-                    }
-                    Position { x, y: self.pos.y }
-                }
-                Dir::Down => {
-                    let y = self.pos.y.checked_add(1)?;
-                    if y >= self.map.height {
-                        None?
-                    }
-                    Position { x: self.pos.x, y }
-                }
-                Dir::Left => Position {
-                    x: self.pos.x.checked_sub(1)?,
-                    y: self.pos.y,
-                },
-            },
-        };
-
-        if self.map.walls.contains(&neighbor.pos) {
-            None
-        } else {
-            Some(neighbor)
-        }
-    }
-
-    fn get_neighbors(&self) -> Vec<(Self, usize)> {
-        vec![
-            self.get_neighbor(&Dir::Up),
-            self.get_neighbor(&Dir::Right),
-            self.get_neighbor(&Dir::Down),
-            self.get_neighbor(&Dir::Left),
-        ]
-        .into_iter()
-        .filter_map(|c_opt| c_opt.and_then(|c| Some((c, 1))))
-        .collect()
-    }
-}
-
-pub fn part_one(input: &str) -> Option<usize> {
-    // let map = Map::parse(input, 71, 71, 1024).unwrap();
-    let map = Map::parse(input, 7, 7, 12).unwrap();
-    let start = Cell {
-        map: &map,
-        pos: Position { x: 0, y: 0 },
-    };
-    let res = dijkstra(
-        &start,
-        |c| c.get_neighbors(),
-        |c| {
-            c.pos
-                == Position {
-                    x: map.width - 1,
-                    y: map.height - 1,
-                }
-        },
+    let res = search::bfs(
+        [map.start()],
+        |pos| pos.neighbours(size).filter(|n| !walls.contains(n)),
+        |pos| *pos == map.exit(),
     );
-    res.and_then(|path| Some(path.1))
-}
-
-// #### PART 2 ####
-#[derive(Debug)]
-struct ChronoMap {
-    walls: Vec<Position>,
-    width: usize,
-    height: usize,
-    fallen: usize,
+    res.goal_cost()
 }
 
-impl ChronoMap {
-    fn parse(input: &str, width: usize, height: usize) -> Result<Self> {
-        let mut walls = vec![];
-        let re = Regex::new(r"(\d+),(\d+)$")?;
-        for l in input.lines() {
-            let captures = re.captures(l).ok_or(anyhow!("Failed to parse position"))?;
-            let x = captures
-                .get(1)
-                .and_then(|x| x.as_str().parse().ok())
-                .ok_or(anyhow!("Failed to parse x"))?;
-            let y = captures
-                .get(2)
-                .and_then(|y| y.as_str().parse().ok())
-                .ok_or(anyhow!("Failed to parse y"))?;
-            walls.push(Position { x, y });
-        }
-        Ok(Self {
-            walls,
-            width,
-            height,
-            fallen: 0,
-        })
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
-struct ChronoCell<'a> {
-    pos: Position,
-    map: &'a ChronoMap,
-}
-
-impl<'a> Hash for ChronoCell<'a> {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.pos.hash(state);
-    }
-}
-
-impl<'a> PartialEq for ChronoCell<'a> {
-    fn eq(&self, other: &Self) -> bool {
-        self.pos == other.pos
-    }
+pub fn part_one(input: &str) -> Option<usize> {
+    shortest_path(input, SIZE, FALLEN)
 }
 
-impl<'a> Eq for ChronoCell<'a> {}
-
-impl<'a> ChronoCell<'a> {
-    fn get_neighbor(&self, dir: &Dir) -> Option<Self> {
-        let neighbor = ChronoCell {
-            map: self.map,
-            pos: match dir {
-                Dir::Up => Position {
-                    x: self.pos.x,
-                    y: self.pos.y.checked_sub(1)?,
-                },
-                Dir::Right => {
-                    let x = self.pos.x.checked_add(1)?;
-                    if x >= self.map.width {
-                        None?
-                    }
-                    Position { x, y: self.pos.y }
-                }
-                Dir::Down => {
-                    let y = self.pos.y.checked_add(1)?;
-                    if y >= self.map.height {
-                        None?
-                    }
-                    Position { x: self.pos.x, y }
-                }
-                Dir::Left => Position {
-                    x: self.pos.x.checked_sub(1)?,
-                    y: self.pos.y,
-                },
-            },
-        };
-
-        let iswall = !neighbor
-            .map
-            .walls
-            .iter()
-            .take(neighbor.map.fallen)
-            .fold(true, |acc, pos| acc && (pos != &neighbor.pos));
-
-        if iswall {
-            None
-        } else {
-            Some(neighbor)
-        }
-    }
+/*
+ * Bytes only ever block cells, so the first one that cuts the exit off is found with a single
+ * union-find pass back in time, instead of a search after every byte
+ */
+fn first_blocking(input: &str, size: usize) -> Option<String> {
+    let map = Map::parse(input, size).unwrap();
+    let nodes = GridNodes::new((map.size, map.size));
+    let blocked: Vec<usize> = map.bytes.iter().map(|p| nodes.cell(*p)).collect();
 
-    fn get_neighbors(&self) -> Vec<Self> {
-        vec![
-            self.get_neighbor(&Dir::Up),
-            self.get_neighbor(&Dir::Right),
-            self.get_neighbor(&Dir::Down),
-            self.get_neighbor(&Dir::Left),
-        ]
-        .into_iter()
-        .filter_map(|c_opt| c_opt)
-        .collect()
-    }
+    let mut timeline = Timeline::new(nodes.count(), |n| nodes.neighbours(n));
+    let cut =
+        timeline.first_disconnection(&blocked, nodes.cell(map.start()), nodes.cell(map.exit()))?;
+    Some(map.bytes[cut].to_string())
 }
 
 pub fn part_two(input: &str) -> Option<String> {
-    let mut map = ChronoMap::parse(input, 71, 71).unwrap();
-    // let mut map = ChronoMap::parse(input, 7, 7).unwrap();
-    map.fallen += 1;
-
-    for i in 0..map.walls.len() {
-        println!("{}/{}", i, map.walls.len());
-        let start = ChronoCell {
-            map: &map,
-            pos: Position { x: 0, y: 0 },
-        };
-
-        let res = dfs(
-            start,
-            |c| c.get_neighbors(),
-            |c| {
-                c.pos
-                    == Position {
-                        x: map.width - 1,
-                        y: map.height - 1,
-                    }
-            },
-        );
-
-        if let None = res {
-            let wall = &map.walls[i];
-            println!("{}", i);
-            return Some(format!("{},{}", wall.x, wall.y));
-        }
-
-        map.fallen += 1;
-    }
-
-    None
+    first_blocking(input, SIZE)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /*
+     * The example is played on a smaller grid, with fewer bytes fallen for part one
+     */
+    const EXAMPLE_SIZE: usize = 7;
+    const EXAMPLE_FALLEN: usize = 12;
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = shortest_path(
+            &advent_of_code::template::read_file("examples", DAY),
+            EXAMPLE_SIZE,
+            EXAMPLE_FALLEN,
+        );
         assert_eq!(result, Some(22));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = first_blocking(
+            &advent_of_code::template::read_file("examples", DAY),
+            EXAMPLE_SIZE,
+        );
         assert_eq!(result, Some("6,1".to_owned()));
     }
+
+    #[test]
+    fn test_scale_input() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let scaled = repeat_after(&input, 4, EXAMPLE_FALLEN);
+        let lines = input.lines().count();
+        assert_eq!(
+            scaled.lines().count(),
+            EXAMPLE_FALLEN + 4 * (lines - EXAMPLE_FALLEN)
+        );
+        assert_eq!(
+            shortest_path(&scaled, EXAMPLE_SIZE, EXAMPLE_FALLEN),
+            Some(22)
+        );
+        assert_eq!(
+            first_blocking(&scaled, EXAMPLE_SIZE),
+            Some("6,1".to_owned())
+        );
+    }
 }
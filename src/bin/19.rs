@@ -4,11 +4,57 @@ use std::{
     rc::{Rc, Weak},
 };
 
+use advent_of_code::{memo::Memo, trie::Trie};
 use anyhow::{anyhow, Result};
 use regex::Regex;
 
-advent_of_code::solution!(19);
+advent_of_code::solution!(19, variants {
+    part_one => [part_one_regex],
+    part_two => [part_two_nfa],
+});
 
+/*
+ * The towels, as a trie, and the designs to make with them
+ */
+fn parse(input: &str) -> Result<(Trie, Vec<&str>)> {
+    let mut lines = input.lines();
+    let towels = Trie::from_patterns(
+        lines
+            .next()
+            .ok_or(anyhow!("Not enough lines !"))?
+            .split(", "),
+    );
+    lines.next();
+    Ok((towels, lines.collect()))
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let (towels, designs) = parse(input).unwrap();
+    Some(
+        designs
+            .iter()
+            .filter(|d| towels.count_segmentations(d.as_bytes()) > 0)
+            .count() as u32,
+    )
+}
+
+/*
+ * The number of arrangements of a design is counted from its end, each offset summing the
+ * counts after the towels starting there
+ */
+pub fn part_two(input: &str) -> Option<u64> {
+    let (towels, designs) = parse(input).unwrap();
+    Some(
+        designs
+            .iter()
+            .map(|d| towels.count_segmentations(d.as_bytes()))
+            .sum(),
+    )
+}
+
+/*
+ * Reference implementations, kept as variants
+ */
 fn parse_regex(line: &str) -> Result<Regex> {
     let mut regex = "^(".to_owned();
     regex.extend(line.replace(", ", "|").chars());
@@ -19,7 +65,7 @@ fn parse_regex(line: &str) -> Result<Regex> {
 /*
  * A regex is just an NFA, which is exactly what we need here.
  */
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one_regex(input: &str) -> Option<u32> {
     let mut lines = input.lines();
     let regex = parse_regex(lines.next().ok_or(anyhow!("Not enough lines !")).unwrap()).unwrap();
     lines.next();
@@ -29,7 +75,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 /*
  * Part two : for fun, I reimplemented an NFA to count
  * how many paths lead to a valid token
- * -> It works, but very inefficient :(
+ * -> It works, and caching the counts from S makes it fast enough
  */
 #[derive(Debug)]
 enum State {
@@ -87,9 +133,15 @@ impl NFA {
     }
 
     /*
-     * Recursive function to compute how many paths lead to a valid solution
+     * Recursive function to compute how many paths lead to a valid solution.
+     * Back at S, the count only depends on what is left of the design, so it is cached
      */
-    fn _count(&self, design: &str, curr_state: &Rc<State>) -> Result<u32> {
+    fn _count<'a>(
+        &self,
+        design: &'a str,
+        curr_state: &Rc<State>,
+        memo: &mut Memo<&'a str, u64>,
+    ) -> Result<u64> {
         let len = design.len();
         let curr_state = curr_state.as_ref();
 
@@ -107,22 +159,20 @@ impl NFA {
         let c = design.chars().next().unwrap(); // Unwrap ok here because of previous condition
         match curr_state {
             // For S only, need to sum how many valid paths are available
-            State::Start(next) => match next.borrow().get(&c) {
-                Some(next_states) => next_states.iter().fold(Ok(0), |acc, state| match acc {
-                    Ok(acc) => Ok(acc
-                        + self._count(
-                            &design,
-                            &state
-                                .upgrade()
-                                .ok_or(anyhow!("Failed to upgrade weak pointer"))?,
-                        )?),
-                    Err(err) => Err(err),
-                }),
-                None => Ok(0),
-            },
+            State::Start(next) => memo.try_get(design, |memo, design| {
+                let Some(next_states) = next.borrow().get(&c).cloned() else {
+                    return Ok(0);
+                };
+                next_states.iter().try_fold(0, |acc, state| {
+                    let state = state
+                        .upgrade()
+                        .ok_or(anyhow!("Failed to upgrade weak pointer"))?;
+                    Ok(acc + self._count(design, &state, memo)?)
+                })
+            }),
             // Else, consume one more char
             State::Transition(next) => match next.get(&c) {
-                Some(next_state) => self._count(&design[1..], next_state),
+                Some(next_state) => self._count(&design[1..], next_state, memo),
                 None => Ok(0),
             },
         }
@@ -131,22 +181,26 @@ impl NFA {
     /*
      * Returns the number of possible ways to come up with design <design>
      */
-    fn count(&self, design: &str) -> Result<u32> {
-        self._count(design, &self.start)
+    fn count<'a>(&self, design: &'a str, memo: &mut Memo<&'a str, u64>) -> Result<u64> {
+        self._count(design, &self.start, memo)
     }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    // let mut lines = input.lines();
-    // let nfa = NFA::parse(lines.next().ok_or(anyhow!("Not enough lines !")).unwrap()).unwrap();
-    // lines.next();
-    // Some(lines.fold(0, |acc, l| acc + nfa.count(l).unwrap()))
-    None
+pub fn part_two_nfa(input: &str) -> Option<u64> {
+    let mut lines = input.lines();
+    let nfa = NFA::parse(lines.next().ok_or(anyhow!("Not enough lines !")).unwrap()).unwrap();
+    lines.next();
+    let mut memo = Memo::new();
+    Some(lines.fold(0, |acc, l| acc + nfa.count(l, &mut memo).unwrap()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::{
+        differential::{check, Generator},
+        rng::Rng,
+    };
 
     #[test]
     fn test_part_one() {
@@ -159,4 +213,103 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(16));
     }
+
+    #[test]
+    fn test_part_one_regex() {
+        let result = part_one_regex(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(6));
+    }
+
+    #[test]
+    fn test_part_two_nfa() {
+        let result = part_two_nfa(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(16));
+    }
+
+    /*
+     * Random towels of up to 3 stripes, and designs made mostly of towels, with a stripe
+     * changed now and then so that some of them are impossible
+     */
+    struct Onsen;
+
+    impl Generator for Onsen {
+        type Value = (Vec<String>, Vec<String>);
+
+        fn generate(&self, rng: &mut Rng) -> Self::Value {
+            let stripes = [b'w', b'u', b'b', b'r', b'g'];
+            let mut towels: Vec<String> = vec![];
+            for _ in 0..rng.range(1..=8) {
+                let len = rng.range(1..=3);
+                let towel: String = (0..len).map(|_| *rng.choose(&stripes) as char).collect();
+                if !towels.contains(&towel) {
+                    towels.push(towel);
+                }
+            }
+
+            let designs = (0..rng.range(1..=10))
+                .map(|_| {
+                    let mut design = vec![];
+                    for _ in 0..rng.range(1..=8) {
+                        design.extend(rng.choose(&towels).bytes());
+                    }
+                    if rng.chance(0.3) {
+                        let i = rng.index(design.len());
+                        design[i] = *rng.choose(&stripes);
+                    }
+                    String::from_utf8(design).unwrap()
+                })
+                .collect();
+
+            (towels, designs)
+        }
+
+        fn render(&self, (towels, designs): &Self::Value) -> String {
+            format!("{}\n\n{}\n", towels.join(", "), designs.join("\n"))
+        }
+
+        fn shrink(&self, (towels, designs): &Self::Value) -> Vec<Self::Value> {
+            let mut candidates = vec![];
+            for i in 0..designs.len() {
+                let mut smaller = designs.clone();
+                smaller.remove(i);
+                if !smaller.is_empty() {
+                    candidates.push((towels.clone(), smaller));
+                }
+            }
+            for i in 0..towels.len() {
+                let mut smaller = towels.clone();
+                smaller.remove(i);
+                if !smaller.is_empty() {
+                    candidates.push((smaller, designs.clone()));
+                }
+            }
+            candidates
+        }
+    }
+
+    #[test]
+    fn test_part_one_differential() {
+        check(
+            DAY,
+            1,
+            &Onsen,
+            &[
+                ("part_one_regex", part_one_regex as fn(&str) -> _),
+                ("part_one", part_one),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_part_two_differential() {
+        check(
+            DAY,
+            2,
+            &Onsen,
+            &[
+                ("part_two_nfa", part_two_nfa as fn(&str) -> _),
+                ("part_two", part_two),
+            ],
+        );
+    }
 }
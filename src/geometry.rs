@@ -0,0 +1,382 @@
+//! Points and directions on a grid.
+//!
+//! As in [`crate::grid`], `x` is the column and `y` the row, so going [`Direction::Down`]
+//! increases `y`. Positions in a grid are unsigned [`Position`]s, and moves between them are
+//! signed [`Offset`]s.
+//!
+//! ```
+//! # use advent_of_code::geometry::{Direction, Position};
+//! let start = Position::new(0, 0);
+//! assert_eq!(start.step(Direction::Up, (3, 3)), None);
+//! assert_eq!(start.step(Direction::Down, (3, 3)), Some(Position::new(0, 1)));
+//! assert_eq!(start.wrapping_step(Direction::Left, (3, 3)), Position::new(2, 0));
+//! ```
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A position in a grid.
+pub type Position = Point<usize>;
+
+/// A move, or a position on an unbounded plane.
+pub type Offset = Point<isize>;
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Position {
+    pub fn signed(self) -> Offset {
+        Point::new(self.x.cast_signed(), self.y.cast_signed())
+    }
+
+    /// Moves by an offset, or `None` when leaving a grid of size `(width, height)`.
+    pub fn step(self, offset: impl Into<Offset>, (width, height): (usize, usize)) -> Option<Self> {
+        let offset = offset.into();
+        let x = self.x.checked_add_signed(offset.x).filter(|x| *x < width)?;
+        let y = self
+            .y
+            .checked_add_signed(offset.y)
+            .filter(|y| *y < height)?;
+        Some(Point::new(x, y))
+    }
+
+    /// Moves by an offset on a grid of size `(width, height)` whose opposite borders are joined,
+    /// so leaving it on one side comes back in on the other. The offset can be of any length.
+    pub fn wrapping_step(self, offset: impl Into<Offset>, (width, height): (usize, usize)) -> Self {
+        self.signed().wrap(offset.into(), (width, height))
+    }
+
+    /// The neighbours in the four directions that are inside a grid of size `(width, height)`.
+    pub fn neighbours(self, size: (usize, usize)) -> impl Iterator<Item = Self> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(direction, size))
+    }
+
+    /// The neighbours in the eight directions that are inside a grid of size `(width, height)`.
+    pub fn neighbours8(self, size: (usize, usize)) -> impl Iterator<Item = Self> {
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(direction, size))
+    }
+
+    pub fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Offset {
+    /// The position, or `None` when it is outside a grid of size `(width, height)`.
+    pub fn unsigned(self, (width, height): (usize, usize)) -> Option<Position> {
+        let x = usize::try_from(self.x).ok().filter(|x| *x < width)?;
+        let y = usize::try_from(self.y).ok().filter(|y| *y < height)?;
+        Some(Point::new(x, y))
+    }
+
+    /// Like [`Position::wrapping_step`], from a point that may be outside the grid.
+    pub fn wrap(self, offset: Offset, (width, height): (usize, usize)) -> Position {
+        let (width, height) = (width.cast_signed(), height.cast_signed());
+        Point::new(
+            (self.x + offset.x).rem_euclid(width).cast_unsigned(),
+            (self.y + offset.y).rem_euclid(height).cast_unsigned(),
+        )
+    }
+
+    pub fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+/// One of the four directions along the axes, in clockwise order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Turns 90° clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Turns 90° counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    pub fn offset(self) -> Offset {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    /// The arrow used for this direction in puzzles: `^`, `>`, `v` or `<`.
+    pub fn to_char(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+}
+
+/// A character that is not a direction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseDirectionError(pub char);
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not a direction", self.0)
+    }
+}
+
+impl Error for ParseDirectionError {}
+
+/// Parses an arrow (`^>v<`) or a letter (`URDL`).
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'U' => Ok(Direction::Up),
+            '>' | 'R' => Ok(Direction::Right),
+            'v' | 'D' => Ok(Direction::Down),
+            '<' | 'L' => Ok(Direction::Left),
+            c => Err(ParseDirectionError(c)),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl From<Direction> for Offset {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
+
+/// One of the eight directions to neighbouring cells, including diagonals, in clockwise order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// Turns 45° clockwise.
+    pub fn rotate_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns 45° counter-clockwise.
+    pub fn rotate_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    pub fn offset(self) -> Offset {
+        let (x, y) = match self {
+            Direction8::North => (0, -1),
+            Direction8::NorthEast => (1, -1),
+            Direction8::East => (1, 0),
+            Direction8::SouthEast => (1, 1),
+            Direction8::South => (0, 1),
+            Direction8::SouthWest => (-1, 1),
+            Direction8::West => (-1, 0),
+            Direction8::NorthWest => (-1, -1),
+        };
+        Point::new(x, y)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+impl From<Direction8> for Offset {
+    fn from(direction: Direction8) -> Self {
+        direction.offset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Direction8, Offset, Position};
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Right.reverse(), Direction::Left);
+        assert_eq!(Direction8::NorthWest.rotate_right(), Direction8::North);
+        assert_eq!(Direction8::North.rotate_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::NorthEast.reverse(), Direction8::SouthWest);
+        assert_eq!(Direction8::from(Direction::Down), Direction8::South);
+
+        for direction in Direction8::ALL {
+            assert_eq!(direction.reverse().offset(), -direction.offset());
+        }
+    }
+
+    #[test]
+    fn converts_chars() {
+        for direction in Direction::ALL {
+            assert_eq!(Direction::try_from(direction.to_char()), Ok(direction));
+        }
+        assert_eq!(Direction::try_from('R'), Ok(Direction::Right));
+        assert!(Direction::try_from('x').is_err());
+    }
+
+    #[test]
+    fn steps_within_bounds() {
+        let size = (3, 2);
+        let corner = Position::new(2, 1);
+        assert_eq!(corner.step(Direction::Right, size), None);
+        assert_eq!(corner.step(Direction::Down, size), None);
+        assert_eq!(corner.step(Direction::Up, size), Some(Position::new(2, 0)));
+        assert_eq!(
+            corner.neighbours(size).collect::<Vec<_>>(),
+            vec![Position::new(2, 0), Position::new(1, 1)]
+        );
+        assert_eq!(corner.neighbours8(size).count(), 3);
+        assert_eq!(Offset::new(-1, 0).unsigned(size), None);
+        assert_eq!(Offset::new(1, 1).unsigned(size), Some(Position::new(1, 1)));
+    }
+
+    #[test]
+    fn wraps_around() {
+        let size = (11, 7);
+        let robot = Position::new(2, 4);
+        let velocity = Offset::new(2, -3);
+        assert_eq!(robot.wrapping_step(velocity, size), Position::new(4, 1));
+        assert_eq!(robot.wrapping_step(velocity * 5, size), Position::new(1, 3));
+        assert_eq!(robot.wrapping_step(velocity * 77, size), robot);
+    }
+}
@@ -0,0 +1,413 @@
+//! Two-dimensional grids of cells, as found in most puzzle inputs.
+//!
+//! Positions are `(x, y)` pairs, with `x` the column and `y` the row, from the top-left corner.
+//!
+//! ```
+//! # use advent_of_code::grid::Grid;
+//! let grid: Grid<char> = Grid::parse("#.\n.#\n").unwrap();
+//! assert_eq!(grid.get((1, 0)), Some(&'.'));
+//! assert_eq!(grid.find(&'#'), Some((0, 0)));
+//! ```
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::geometry::Position;
+
+/// Why a text could not be parsed into a grid. Lines and columns start at 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    /// A line is shorter or longer than the first one.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character could not be converted to a cell.
+    Cell {
+        line: usize,
+        column: usize,
+        char: char,
+        message: String,
+    },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "grid is empty"),
+            ParseError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line}: expected {expected} cells like the first line, found {found}"
+            ),
+            ParseError::Cell {
+                line,
+                column,
+                char,
+                message,
+            } => write!(
+                f,
+                "line {line}, column {column}: invalid cell {char:?}: {message}"
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Cells in row-major order.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid filled with copies of a value.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from cells in row-major order, or `None` if their number does not match.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Option<Self> {
+        (cells.len() == width * height).then_some(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses one row per line, converting each character with `cell`.
+    pub fn parse_with<E: Display>(
+        input: &str,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (y, line) in input.lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+            let mut found = 0;
+
+            for (x, c) in line.chars().enumerate() {
+                let value = cell(c).map_err(|e| ParseError::Cell {
+                    line: y + 1,
+                    column: x + 1,
+                    char: c,
+                    message: e.to_string(),
+                })?;
+                cells.push(value);
+                found += 1;
+            }
+
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    // a trailing blank line ends the grid rather than being a short row.
+                    if found == 0 && input.lines().skip(y).all(|l| l.trim().is_empty()) {
+                        break;
+                    }
+                    return Err(ParseError::Ragged {
+                        line: y + 1,
+                        expected,
+                        found,
+                    });
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        match width {
+            None | Some(0) => Err(ParseError::Empty),
+            Some(width) => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+        }
+    }
+
+    /// Parses one row per line, converting each character with [`TryFrom<char>`].
+    pub fn parse(input: &str) -> Result<Self, ParseError>
+    where
+        T: TryFrom<char>,
+        T::Error: Display,
+    {
+        Self::parse_with(input, T::try_from)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// `(width, height)`, the bounds for moving in [`crate::geometry`].
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Whether a position is inside the grid. Takes signed coordinates, so neighbours of cells on
+    /// the border can be checked without underflowing.
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        usize::try_from(x).is_ok_and(|x| x < self.width)
+            && usize::try_from(y).is_ok_and(|y| y < self.height)
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// Like [`Grid::get`], with signed coordinates.
+    pub fn get_signed(&self, x: isize, y: isize) -> Option<&T> {
+        self.get((usize::try_from(x).ok()?, usize::try_from(y).ok()?))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Position of the first cell, row by row, with the given value.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    /// Positions of all cells with the given value.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(position, _)| position)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Cells from a position, moving by `(dx, dy)` until leaving the grid.
+    pub fn ray(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> impl Iterator<Item = &T> {
+        let (mut x, mut y) = (x.cast_signed(), y.cast_signed());
+        std::iter::from_fn(move || {
+            let cell = self.get_signed(x, y)?;
+            (x, y) = (x + dx, y + dy);
+            Some(cell)
+        })
+    }
+
+    /// Diagonals going down to the right, from the one starting in the top-right corner to the
+    /// one starting in the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.width)
+            .rev()
+            .map(|x| (x, 0))
+            .chain((1..self.height).map(|y| (0, y)));
+        starts.map(|start| self.ray(start, (1, 1)))
+    }
+
+    /// Diagonals going down to the left, from the one starting in the top-left corner to the one
+    /// starting in the bottom-right corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.width)
+            .map(|x| (x, 0))
+            .chain((1..self.height).map(|y| (self.width - 1, y)));
+        starts.map(|start| self.ray(start, (-1, 1)))
+    }
+
+    /// Converts every cell, keeping the shape of the grid.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Renders the grid to text, one line per row, converting each cell with `cell`.
+    pub fn render_with(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            s.extend(row.iter().map(&mut cell));
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("position {position:?} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("position {position:?} is outside of the grid"))
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        &self[(position.x, position.y)]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        &mut self[(position.x, position.y)]
+    }
+}
+
+/// Renders the grid one line per row, with the [`Display`] of each cell.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, ParseError};
+
+    const INPUT: &str = "abc\ndef\n";
+
+    fn grid() -> Grid<char> {
+        Grid::parse(INPUT).unwrap()
+    }
+
+    fn collect<'a>(lines: impl Iterator<Item = impl Iterator<Item = &'a char>>) -> Vec<String> {
+        lines.map(|line| line.collect()).collect()
+    }
+
+    #[test]
+    fn parses_and_renders() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.to_string(), INPUT);
+        assert_eq!(grid.render_with(|c| c.to_ascii_uppercase()), "ABC\nDEF\n");
+    }
+
+    #[test]
+    fn parses_with_conversion() {
+        let grid = Grid::parse_with("12\n34", |c| c.to_digit(10).ok_or("not a digit")).unwrap();
+        assert_eq!(grid.row(1), &[3, 4]);
+
+        let error = Grid::parse_with("12\n3x", |c| c.to_digit(10).ok_or("not a digit"));
+        assert_eq!(
+            error,
+            Err(ParseError::Cell {
+                line: 2,
+                column: 2,
+                char: 'x',
+                message: "not a digit".into()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_ragged_and_empty_input() {
+        assert_eq!(
+            Grid::<char>::parse("abc\nde\n"),
+            Err(ParseError::Ragged {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(Grid::<char>::parse("\n"), Err(ParseError::Empty));
+        assert!(Grid::<char>::parse("ab\ncd\n\n").is_ok());
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let grid = grid();
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(0, 1), Some(&'d'));
+        assert!(grid.contains(2, 1));
+        assert!(!grid.contains(2, 2));
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid: Grid<char> = Grid::parse("#.#\n..#\n").unwrap();
+        assert_eq!(grid.find(&'#'), Some((0, 0)));
+        assert_eq!(
+            grid.find_all(&'#').collect::<Vec<_>>(),
+            vec![(0, 0), (2, 0), (2, 1)]
+        );
+        assert_eq!(grid.find(&'x'), None);
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = grid();
+        assert_eq!(collect(grid.columns()), vec!["ad", "be", "cf"]);
+        assert_eq!(collect(grid.diagonals()), vec!["c", "bf", "ae", "d"]);
+        assert_eq!(collect(grid.anti_diagonals()), vec!["a", "bd", "ce", "f"]);
+        assert_eq!(grid.rows().count(), 2);
+    }
+}
@@ -0,0 +1,291 @@
+//! Runs of consecutive blocks, and the free space left between them.
+//!
+//! [`FreeList`] keeps track of which blocks of a line are free, merging adjacent free runs and
+//! splitting them as blocks get allocated. It finds the leftmost free run of a given length in
+//! logarithmic time.
+//!
+//! ```
+//! # use advent_of_code::intervals::{FreeList, Span};
+//! let mut free = FreeList::new(10);
+//! free.free(Span::new(2, 2));
+//! free.free(Span::new(6, 4));
+//! assert_eq!(free.first_fit(3, 10), Some(6));
+//! assert_eq!(free.first_fit(3, 8), None);
+//!
+//! free.free(Span::new(4, 2));
+//! assert_eq!(free.spans(), vec![Span::new(2, 8)]);
+//! assert_eq!(Span::new(2, 3).checksum(5), (2 + 3 + 4) * 5);
+//! ```
+
+/// A run of `len` blocks from `start`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, len: usize) -> Self {
+        Span { start, len }
+    }
+
+    /// The position just after the last block.
+    pub fn end(&self) -> usize {
+        self.start + self.len
+    }
+
+    pub fn contains(&self, position: usize) -> bool {
+        (self.start..self.end()).contains(&position)
+    }
+
+    /// The sum of `position * id` over the blocks of the run.
+    pub fn checksum(&self, id: u64) -> u64 {
+        let (start, len) = (self.start as u64, self.len as u64);
+        id * (start * len + len * len.saturating_sub(1) / 2)
+    }
+}
+
+/// The free runs within a node of the tree: the longest one, and the ones at both ends.
+#[derive(Clone, Copy, Debug, Default)]
+struct Node {
+    prefix: usize,
+    suffix: usize,
+    longest: usize,
+    /// Set when the whole node was freed or allocated, but its children were not updated yet.
+    pending: Option<bool>,
+}
+
+/// Which blocks of `0..len` are free, as a segment tree.
+#[derive(Clone, Debug)]
+pub struct FreeList {
+    len: usize,
+    nodes: Vec<Node>,
+}
+
+impl FreeList {
+    /// Every block allocated.
+    pub fn new(len: usize) -> Self {
+        FreeList {
+            len,
+            nodes: vec![Node::default(); 4 * len.max(1)],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The length of the longest free run.
+    pub fn longest(&self) -> usize {
+        self.nodes[0].longest
+    }
+
+    /// Frees the blocks of a span, merging them with the free runs around it.
+    pub fn free(&mut self, span: Span) {
+        self.set(0, 0, self.len, span, true);
+    }
+
+    /// Allocates the blocks of a span, splitting the free runs it overlaps.
+    pub fn allocate(&mut self, span: Span) {
+        self.set(0, 0, self.len, span, false);
+    }
+
+    pub fn is_free(&self, position: usize) -> bool {
+        let (mut node, mut l, mut r) = (0, 0, self.len);
+        if position >= r {
+            return false;
+        }
+        loop {
+            if let Some(free) = self.nodes[node].pending {
+                return free;
+            }
+            if r - l == 1 {
+                return self.nodes[node].longest == 1;
+            }
+            let m = (l + r) / 2;
+            (node, l, r) = if position < m {
+                (2 * node + 1, l, m)
+            } else {
+                (2 * node + 2, m, r)
+            };
+        }
+    }
+
+    /// The start of the leftmost free run of at least `len` blocks, if it ends before `before`.
+    pub fn first_fit(&self, len: usize, before: usize) -> Option<usize> {
+        if len == 0 || self.longest() < len {
+            return None;
+        }
+
+        let (mut node, mut l, mut r) = (0, 0, self.len);
+        let start = loop {
+            if self.nodes[node].pending == Some(true) || r - l == 1 {
+                break l;
+            }
+            let m = (l + r) / 2;
+            let (left, right) = (self.nodes[2 * node + 1], self.nodes[2 * node + 2]);
+            if left.longest >= len {
+                (node, r) = (2 * node + 1, m);
+            } else if left.suffix + right.prefix >= len {
+                break m - left.suffix;
+            } else {
+                (node, l) = (2 * node + 2, m);
+            }
+        };
+
+        (start + len <= before).then_some(start)
+    }
+
+    /// The free runs, from left to right, adjacent free blocks being merged.
+    pub fn spans(&self) -> Vec<Span> {
+        let mut spans: Vec<Span> = vec![];
+        self.collect(0, 0, self.len, &mut spans);
+        spans
+    }
+
+    fn collect(&self, node: usize, l: usize, r: usize, spans: &mut Vec<Span>) {
+        let current = self.nodes[node];
+        if l == r || current.longest == 0 {
+            return;
+        }
+        if current.longest == r - l {
+            match spans.last_mut() {
+                Some(last) if last.end() == l => last.len += r - l,
+                _ => spans.push(Span::new(l, r - l)),
+            }
+            return;
+        }
+        let m = (l + r) / 2;
+        self.collect(2 * node + 1, l, m, spans);
+        self.collect(2 * node + 2, m, r, spans);
+    }
+
+    fn fill(&mut self, node: usize, len: usize, free: bool) {
+        let run = if free { len } else { 0 };
+        self.nodes[node] = Node {
+            prefix: run,
+            suffix: run,
+            longest: run,
+            pending: Some(free),
+        };
+    }
+
+    fn set(&mut self, node: usize, l: usize, r: usize, span: Span, free: bool) {
+        if span.end() <= l || r <= span.start || l == r {
+            return;
+        }
+        if span.start <= l && r <= span.end() {
+            self.fill(node, r - l, free);
+            return;
+        }
+
+        let m = (l + r) / 2;
+        let (left, right) = (2 * node + 1, 2 * node + 2);
+        if let Some(pending) = self.nodes[node].pending.take() {
+            self.fill(left, m - l, pending);
+            self.fill(right, r - m, pending);
+        }
+        self.set(left, l, m, span, free);
+        self.set(right, m, r, span, free);
+
+        let (a, b) = (self.nodes[left], self.nodes[right]);
+        self.nodes[node] = Node {
+            prefix: if a.prefix == m - l {
+                a.prefix + b.prefix
+            } else {
+                a.prefix
+            },
+            suffix: if b.suffix == r - m {
+                a.suffix + b.suffix
+            } else {
+                b.suffix
+            },
+            longest: a.longest.max(b.longest).max(a.suffix + b.prefix),
+            pending: None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_runs() {
+        assert_eq!(Span::new(0, 0).checksum(7), 0);
+        assert_eq!(Span::new(4, 1).checksum(7), 28);
+        let span = Span::new(10, 5);
+        let expanded: u64 = (10..15).map(|p| p * 3).sum();
+        assert_eq!(span.checksum(3), expanded);
+        assert_eq!(span.end(), 15);
+        assert!(span.contains(14) && !span.contains(15));
+    }
+
+    #[test]
+    fn splits_and_merges_free_runs() {
+        let mut free = FreeList::new(13);
+        assert_eq!(free.spans(), vec![]);
+
+        free.free(Span::new(0, 13));
+        free.allocate(Span::new(3, 2));
+        free.allocate(Span::new(9, 1));
+        assert_eq!(
+            free.spans(),
+            vec![Span::new(0, 3), Span::new(5, 4), Span::new(10, 3)]
+        );
+        assert_eq!(free.longest(), 4);
+        assert!(free.is_free(2) && !free.is_free(3) && !free.is_free(13));
+
+        free.free(Span::new(9, 1));
+        assert_eq!(free.spans(), vec![Span::new(0, 3), Span::new(5, 8)]);
+        free.free(Span::new(4, 1));
+        assert_eq!(free.spans(), vec![Span::new(0, 3), Span::new(4, 9)]);
+    }
+
+    #[test]
+    fn finds_leftmost_fit() {
+        let mut free = FreeList::new(20);
+        for span in [(1, 2), (5, 4), (11, 3), (15, 5)] {
+            free.free(Span::new(span.0, span.1));
+        }
+
+        assert_eq!(free.first_fit(1, 20), Some(1));
+        assert_eq!(free.first_fit(3, 20), Some(5));
+        assert_eq!(free.first_fit(5, 20), Some(15));
+        assert_eq!(free.first_fit(5, 19), None);
+        assert_eq!(free.first_fit(6, 20), None);
+        assert_eq!(free.first_fit(0, 20), None);
+
+        // a run across the middle of the tree.
+        free.free(Span::new(9, 2));
+        assert_eq!(free.first_fit(8, 20), Some(5));
+    }
+
+    #[test]
+    fn agrees_with_blocks() {
+        // frees and allocates pseudo-random spans, checking against a plain list of blocks.
+        let mut blocks = [false; 37];
+        let mut free = FreeList::new(blocks.len());
+        let mut seed = 7usize;
+        for step in 0..200 {
+            seed = (seed * 1103515245 + 12345) % (1 << 31);
+            let span = Span::new(seed % 37, (seed >> 8) % 6);
+            let span = Span::new(span.start, span.len.min(37 - span.start));
+            blocks[span.start..span.end()].fill(step % 3 != 0);
+            if step % 3 != 0 {
+                free.free(span);
+            } else {
+                free.allocate(span);
+            }
+
+            let len = (seed >> 12) % 5 + 1;
+            let expected = (0..=37 - len).find(|s| blocks[*s..*s + len].iter().all(|b| *b));
+            assert_eq!(free.first_fit(len, 37), expected);
+            assert!((0..37).all(|p| free.is_free(p) == blocks[p]));
+        }
+    }
+}
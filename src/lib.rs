@@ -1,3 +1,13 @@
+pub mod geometry;
+pub mod grid;
+pub mod intervals;
+pub mod math;
+pub mod memo;
+pub mod order;
+pub mod parse;
+pub mod search;
 pub mod template;
+pub mod trie;
+pub mod union_find;
 
 // Use this file to add helper functions and additional modules.
@@ -1,43 +1,79 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::aoc_cli::DownloadMode;
+use advent_of_code::template::commands::{
+    all, download, fuzz, history, read, scaffold, scale, solve, status, time, verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{aoc_cli::DownloadMode, Day};
     use std::process;
 
     pub enum AppArguments {
         Download {
             day: Day,
+            mode: DownloadMode,
         },
         Read {
             day: Day,
+            pager: bool,
         },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
         },
+        Scale {
+            day: Option<Day>,
+            max_factor: Option<usize>,
+            budget: Option<u64>,
+        },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
+            alloc: bool,
             submit: Option<u8>,
+            force: bool,
+            retry: bool,
         },
         All {
             release: bool,
+            jobs: usize,
+            force: bool,
+        },
+        Fuzz {
+            day: Option<Day>,
+            cases: Option<usize>,
+            timeout: Option<u64>,
+            seed: Option<u64>,
+        },
+        History {
+            day: Option<Day>,
+            svg: Option<String>,
+        },
+        Status {
+            json: bool,
+            skip_tests: bool,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            alloc: bool,
+            jobs: usize,
+        },
+        Verify {
+            release: bool,
+            jobs: usize,
+            force: bool,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -46,36 +82,83 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                force: args.contains("--force"),
+            },
+            Some("fuzz") => AppArguments::Fuzz {
+                cases: args.opt_value_from_str("--cases")?,
+                timeout: args.opt_value_from_str("--timeout")?,
+                seed: args.opt_value_from_str("--seed")?,
+                day: args.opt_free_from_str()?,
+            },
+            Some("history") => AppArguments::History {
+                svg: args.opt_value_from_str("--svg")?,
+                day: args.opt_free_from_str()?,
+            },
+            Some("status") => AppArguments::Status {
+                json: args.contains("--json"),
+                skip_tests: args.contains("--skip-tests"),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let alloc = args.contains("--alloc");
+                let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    alloc,
+                    jobs,
+                }
+            }
+            Some("download") => {
+                let mode = match (
+                    args.contains("--puzzle-only"),
+                    args.contains("--input-only"),
+                ) {
+                    (true, false) => DownloadMode::Puzzle,
+                    (false, true) => DownloadMode::Input,
+                    _ => DownloadMode::Both,
+                };
+                AppArguments::Download {
+                    day: args.free_from_str()?,
+                    mode,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
+                pager: !args.contains("--no-pager"),
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
+            Some("scale") => AppArguments::Scale {
+                max_factor: args.opt_value_from_str("--max-factor")?,
+                budget: args.opt_value_from_str("--budget")?,
+                day: args.opt_free_from_str()?,
+            },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                alloc: args.contains("--alloc"),
+                force: args.contains("--force"),
+                retry: args.contains("--retry"),
+            },
+            Some("verify") => AppArguments::Verify {
+                release: args.contains("--release"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                force: args.contains("--force"),
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -102,10 +185,28 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                release,
+                jobs,
+                force,
+            } => all::handle(release, jobs, force),
+            AppArguments::Fuzz {
+                day,
+                cases,
+                timeout,
+                seed,
+            } => fuzz::handle(day, cases, timeout, seed),
+            AppArguments::History { day, svg } => history::handle(day, svg),
+            AppArguments::Status { json, skip_tests } => status::handle(json, !skip_tests),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                alloc,
+                jobs,
+            } => time::handle(day, all, store, alloc, jobs),
+            AppArguments::Download { day, mode } => download::handle(day, mode),
+            AppArguments::Read { day, pager } => read::handle(day, pager),
             AppArguments::Scaffold {
                 day,
                 download,
@@ -113,32 +214,30 @@ fn main() {
             } => {
                 scaffold::handle(day, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(day, DownloadMode::Both);
                 }
             }
+            AppArguments::Scale {
+                day,
+                max_factor,
+                budget,
+            } => scale::handle(day, max_factor, budget),
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
+                alloc,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                force,
+                retry,
+            } => solve::handle(day, release, dhat, alloc, submit, force, retry),
+            AppArguments::Verify {
+                release,
+                jobs,
+                force,
+            } => verify::handle(release, jobs, force),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
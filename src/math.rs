@@ -0,0 +1,402 @@
+//! Number theory, and exact solving of small linear systems.
+//!
+//! Puzzle numbers can get large, so computations are done on `i128`, and systems are solved over
+//! exact rationals rather than floats.
+//!
+//! ```
+//! # use advent_of_code::math::{self, Ratio, Solutions};
+//! assert_eq!(math::lcm(101, 103), 10403);
+//! // x = 2 (mod 3), x = 3 (mod 5)
+//! assert_eq!(math::crt(&[(2, 3), (3, 5)]), Some((8, 15)));
+//!
+//! // 94a + 22b = 8400, 34a + 67b = 5400
+//! let solutions = math::solve(&[vec![94, 22], vec![34, 67]], &[8400, 5400]);
+//! assert_eq!(solutions, Solutions::One(vec![Ratio::from(80), Ratio::from(40)]));
+//! ```
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
+
+/// Greatest common divisor of two non-negative numbers.
+pub fn gcd<T>(mut a: T, mut b: T) -> T
+where
+    T: Copy + PartialEq + Default + Rem<Output = T>,
+{
+    while b != T::default() {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple of two non-negative numbers.
+pub fn lcm<T>(a: T, b: T) -> T
+where
+    T: Copy + PartialEq + Default + Rem<Output = T> + Div<Output = T> + Mul<Output = T>,
+{
+    if a == T::default() || b == T::default() {
+        return T::default();
+    }
+    a / gcd(a, b) * b
+}
+
+/// Extended Euclid: `(g, x, y)` such that `a * x + b * y = g`, with `g` the non-negative
+/// greatest common divisor of `a` and `b`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The inverse of `a` modulo `m`, in `0..m`, if `a` and `m` are coprime.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Chinese remainder theorem: the `x` satisfying every `x = residue (mod modulus)` of the
+/// congruences, as `(x, m)` with `m` the least common multiple of the moduli and `x` in `0..m`.
+/// The moduli do not need to be coprime. `None` if the congruences contradict each other.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, m), &(residue, modulus)| {
+            // x + m * k = residue (mod modulus), solved for k.
+            let (g, inverse, _) = extended_gcd(m, modulus);
+            let difference = residue - x;
+            if difference % g != 0 {
+                return None;
+            }
+            let step = modulus / g;
+            let k = (difference / g % step * inverse % step).rem_euclid(step);
+            let m = m * step;
+            Some(((x + m / step * k).rem_euclid(m), m))
+        })
+}
+
+/// An exact fraction, always in lowest terms with a positive denominator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Ratio {
+    /// `numerator / denominator`. Panics if the denominator is zero.
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "denominator is zero");
+        let g = gcd(numerator.abs(), denominator.abs()) * denominator.signum();
+        Ratio {
+            numerator: numerator / g,
+            denominator: denominator / g,
+        }
+    }
+
+    pub fn numerator(self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.numerator == 0
+    }
+
+    /// The value, if it is a whole number.
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+}
+
+impl From<i128> for Ratio {
+    fn from(value: i128) -> Self {
+        Ratio {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+}
+
+impl Display for Ratio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            d => write!(f, "{}/{d}", self.numerator),
+        }
+    }
+}
+
+impl Add for Ratio {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Ratio::new(
+            self.numerator * rhs.denominator + rhs.numerator * self.denominator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl Sub for Ratio {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul for Ratio {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Ratio::new(
+            self.numerator * rhs.numerator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl Div for Ratio {
+    type Output = Self;
+
+    /// Panics when dividing by zero.
+    fn div(self, rhs: Self) -> Self {
+        Ratio::new(
+            self.numerator * rhs.denominator,
+            self.denominator * rhs.numerator,
+        )
+    }
+}
+
+impl Neg for Ratio {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Ratio {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+    }
+}
+
+/// The solutions of a linear system.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Solutions {
+    None,
+    One(Vec<Ratio>),
+    /// Every `particular + t1 * kernel[0] + t2 * kernel[1] + ...`, for any rationals `t1, t2...`.
+    Infinite {
+        particular: Vec<Ratio>,
+        kernel: Vec<Vec<Ratio>>,
+    },
+}
+
+/// Solves `a * x = b` by Gauss-Jordan elimination, for `a` with one row per equation and one
+/// column per unknown.
+pub fn solve(a: &[Vec<i128>], b: &[i128]) -> Solutions {
+    let unknowns = a.first().map_or(0, Vec::len);
+    let mut rows: Vec<Vec<Ratio>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, b)| row.iter().chain([b]).map(|v| Ratio::from(*v)).collect())
+        .collect();
+
+    // reduced row echelon form, keeping the column of the pivot of each row.
+    let mut pivots = vec![];
+    for column in 0..unknowns {
+        let row = pivots.len();
+        let Some(found) = (row..rows.len()).find(|r| !rows[*r][column].is_zero()) else {
+            continue;
+        };
+        rows.swap(row, found);
+
+        let pivot = rows[row][column];
+        for value in &mut rows[row] {
+            *value = *value / pivot;
+        }
+        let pivot_row = rows[row].clone();
+        for (other, values) in rows.iter_mut().enumerate() {
+            let factor = values[column];
+            if other != row && !factor.is_zero() {
+                for (value, p) in values.iter_mut().zip(&pivot_row).skip(column) {
+                    *value = *value - factor * *p;
+                }
+            }
+        }
+        pivots.push(column);
+    }
+
+    // a row left as 0 = b, with b not zero.
+    if rows[pivots.len()..]
+        .iter()
+        .any(|row| !row[unknowns].is_zero())
+    {
+        return Solutions::None;
+    }
+
+    let mut particular = vec![Ratio::from(0); unknowns];
+    for (row, column) in pivots.iter().enumerate() {
+        particular[*column] = rows[row][unknowns];
+    }
+    if pivots.len() == unknowns {
+        return Solutions::One(particular);
+    }
+
+    let kernel = (0..unknowns)
+        .filter(|column| !pivots.contains(column))
+        .map(|free| {
+            let mut vector = vec![Ratio::from(0); unknowns];
+            vector[free] = Ratio::from(1);
+            for (row, column) in pivots.iter().enumerate() {
+                vector[*column] = -rows[row][free];
+            }
+            vector
+        })
+        .collect();
+    Solutions::Infinite { particular, kernel }
+}
+
+/// The cheapest non-negative integers `(x, y)` with `a * x + b * y = target`, where `x` costs
+/// `cost_x` and `y` costs `cost_y`. This is what is left of a system of two unknowns whose
+/// equations are all multiples of one another. `a`, `b` and `target` must not be negative.
+pub fn min_cost_combination(
+    a: i128,
+    b: i128,
+    target: i128,
+    cost_x: i128,
+    cost_y: i128,
+) -> Option<(i128, i128)> {
+    let cost = |(x, y): (i128, i128)| x * cost_x + y * cost_y;
+    match (a, b) {
+        (0, 0) => (target == 0).then_some((0, 0)),
+        (0, b) => (target % b == 0).then_some((0, target / b)),
+        (a, 0) => (target % a == 0).then_some((target / a, 0)),
+        (a, b) => {
+            let (g, x0, y0) = extended_gcd(a, b);
+            if target % g != 0 {
+                return None;
+            }
+            // every solution is (x0 + k * b / g, y0 - k * a / g): y >= 0 bounds k from above,
+            // x >= 0 from below, and the cost is linear in k, so one of the bounds is cheapest.
+            let (x0, y0) = (x0 * (target / g), y0 * (target / g));
+            let (step_x, step_y) = (b / g, a / g);
+            let lowest = (-x0).div_euclid(step_x) + i128::from((-x0).rem_euclid(step_x) != 0);
+            let highest = y0.div_euclid(step_y);
+            if lowest > highest {
+                return None;
+            }
+            [lowest, highest]
+                .map(|k| (x0 + k * step_x, y0 - k * step_y))
+                .into_iter()
+                .min_by_key(|solution| cost(*solution))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_divisors_and_multiples() {
+        assert_eq!(gcd(12u64, 18), 6);
+        assert_eq!(gcd(7usize, 0), 7);
+        assert_eq!(lcm(4i64, 6), 12);
+        assert_eq!(lcm(0u32, 6), 0);
+
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(extended_gcd(-4, 6).0, 2);
+    }
+
+    #[test]
+    fn inverts_and_combines_congruences() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 8), None);
+
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // moduli that are not coprime.
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn computes_with_ratios() {
+        let half = Ratio::new(2, 4);
+        assert_eq!(half, Ratio::new(-1, -2));
+        assert_eq!(half + Ratio::new(1, 3), Ratio::new(5, 6));
+        assert_eq!(half - Ratio::from(1), Ratio::new(-1, 2));
+        assert_eq!((half * Ratio::from(4)).to_integer(), Some(2));
+        assert_eq!(half.to_integer(), None);
+        assert!(Ratio::new(1, 3) < half);
+        assert_eq!(Ratio::new(3, -6).to_string(), "-1/2");
+    }
+
+    #[test]
+    fn solves_systems() {
+        assert_eq!(
+            solve(&[vec![1, 1], vec![1, -1]], &[1, 0]),
+            Solutions::One(vec![Ratio::new(1, 2), Ratio::new(1, 2)])
+        );
+        // parallel lines.
+        assert_eq!(solve(&[vec![1, 2], vec![2, 4]], &[3, 7]), Solutions::None);
+        // the same line twice.
+        assert_eq!(
+            solve(&[vec![1, 2], vec![2, 4]], &[3, 6]),
+            Solutions::Infinite {
+                particular: vec![Ratio::from(3), Ratio::from(0)],
+                kernel: vec![vec![Ratio::from(-2), Ratio::from(1)]],
+            }
+        );
+        // large values, as in the second part of day 13.
+        let target = 10_000_000_000_000i128;
+        let solutions = solve(
+            &[vec![94, 22], vec![34, 67]],
+            &[target + 8400, target + 5400],
+        );
+        assert!(matches!(solutions, Solutions::One(_)));
+    }
+
+    #[test]
+    fn finds_cheapest_combination() {
+        // 3x + 5y = 16: (2, 2) costs 2 * 3 + 2 = 8, (all other solutions are negative).
+        assert_eq!(min_cost_combination(3, 5, 16, 3, 1), Some((2, 2)));
+        // 2x + 4y = 8: (4, 0), (2, 1) or (0, 2), depending on which is cheaper.
+        assert_eq!(min_cost_combination(2, 4, 8, 3, 1), Some((0, 2)));
+        assert_eq!(min_cost_combination(2, 4, 8, 1, 10), Some((4, 0)));
+        assert_eq!(min_cost_combination(2, 4, 7, 1, 1), None);
+        assert_eq!(min_cost_combination(5, 7, 3, 1, 1), None);
+        assert_eq!(min_cost_combination(0, 4, 8, 1, 1), Some((0, 2)));
+    }
+}
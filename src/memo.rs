@@ -0,0 +1,192 @@
+//! Caches for recursive computations that keep solving the same subproblems.
+//!
+//! The computation of a value is given the cache itself, so that it can look up the values it
+//! depends on, recursively.
+//!
+//! ```
+//! # use advent_of_code::memo::Memo;
+//! fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+//!     memo.get(n, |memo, &n| match n {
+//!         0 | 1 => n,
+//!         n => fibonacci(memo, n - 1) + fibonacci(memo, n - 2),
+//!     })
+//! }
+//!
+//! let mut memo = Memo::new();
+//! assert_eq!(fibonacci(&mut memo, 90), 2880067194370816120);
+//! assert_eq!(memo.stats().misses, 91);
+//! ```
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::Infallible,
+    hash::Hash,
+};
+
+/// How a cache has been used so far.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Values found in the cache.
+    pub hits: usize,
+    /// Values that had to be computed.
+    pub misses: usize,
+    /// Values dropped to make room for new ones.
+    pub evictions: usize,
+}
+
+/// Values computed for keys, optionally keeping only the most recently computed ones.
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    order: VecDeque<K>,
+    capacity: Option<usize>,
+    stats: Stats,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Memo<K, V> {
+    /// A cache keeping every value.
+    pub fn new() -> Self {
+        Memo {
+            values: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: None,
+            stats: Stats::default(),
+        }
+    }
+
+    /// A cache keeping at most `capacity` values, dropping the oldest ones first.
+    pub fn bounded(capacity: usize) -> Self {
+        Memo {
+            capacity: Some(capacity),
+            ..Self::new()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Forgets every value, but not the statistics.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.order.clear();
+    }
+
+    /// The value for `key`, from the cache or computed by `compute`.
+    pub fn get(&mut self, key: K, compute: impl FnOnce(&mut Self, &K) -> V) -> V {
+        let Ok(value) = self.try_get(key, |memo, key| Ok::<_, Infallible>(compute(memo, key)));
+        value
+    }
+
+    /// The value for `key`, from the cache or computed by `compute`. Errors are not cached.
+    pub fn try_get<E>(
+        &mut self,
+        key: K,
+        compute: impl FnOnce(&mut Self, &K) -> Result<V, E>,
+    ) -> Result<V, E> {
+        if let Some(value) = self.values.get(&key) {
+            self.stats.hits += 1;
+            return Ok(value.clone());
+        }
+        self.stats.misses += 1;
+
+        let value = compute(self, &key)?;
+        self.insert(key, value.clone());
+        Ok(value)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.capacity == Some(0) {
+            return;
+        }
+        // a recursive computation may already have cached it.
+        if self.values.insert(key.clone(), value).is_some() {
+            return;
+        }
+        if self.capacity.is_some() {
+            self.order.push_back(key);
+        }
+        while self
+            .capacity
+            .is_some_and(|capacity| self.values.len() > capacity)
+        {
+            if let Some(oldest) = self.order.pop_front() {
+                self.values.remove(&oldest);
+                self.stats.evictions += 1;
+            }
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The number of paths from `(0, 0)` to `(x, y)` going right or down.
+    fn paths(memo: &mut Memo<(u64, u64), u64>, (x, y): (u64, u64)) -> u64 {
+        memo.get((x, y), |memo, _| match (x, y) {
+            (0, _) | (_, 0) => 1,
+            _ => paths(memo, (x - 1, y)) + paths(memo, (x, y - 1)),
+        })
+    }
+
+    #[test]
+    fn caches_recursive_values() {
+        let mut memo = Memo::new();
+        assert_eq!(paths(&mut memo, (16, 16)), 601080390);
+        assert_eq!(memo.len(), 17 * 17 - 1);
+        assert_eq!(memo.stats().misses, memo.len());
+
+        let hits = memo.stats().hits;
+        assert_eq!(paths(&mut memo, (16, 16)), 601080390);
+        assert_eq!(memo.stats().hits, hits + 1);
+    }
+
+    #[test]
+    fn drops_oldest_values() {
+        let mut memo = Memo::bounded(2);
+        for n in 0..4 {
+            memo.get(n, |_, n| n * 2);
+        }
+        assert_eq!(memo.len(), 2);
+        assert_eq!(memo.stats().evictions, 2);
+
+        // 3 is still there, 0 was computed again.
+        assert_eq!(memo.get(3, |_, _| 0), 6);
+        assert_eq!(memo.get(0, |_, _| 1), 1);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 1,
+                misses: 5,
+                evictions: 3
+            }
+        );
+
+        // still correct, with a cache too small to help much.
+        let mut memo = Memo::bounded(3);
+        assert_eq!(paths(&mut memo, (6, 6)), 924);
+    }
+
+    #[test]
+    fn does_not_cache_errors() {
+        let mut memo = Memo::new();
+        assert_eq!(memo.try_get("a", |_, _| Err("failed")), Err("failed"));
+        assert_eq!(memo.try_get("a", |_, _| Ok::<_, ()>(1)), Ok(1));
+        assert_eq!(memo.try_get("a", |_, _| Err(())), Ok(1));
+        assert_eq!(memo.stats().misses, 2);
+    }
+}
@@ -0,0 +1,256 @@
+//! Precedence rules between items, and sorting items so that they follow them.
+//!
+//! Rules are pairs `(before, after)`. They need not be consistent as a whole, as long as they are
+//! within the items being sorted: [`Rules::topological_sort`] only looks at the rules between
+//! those, and names the rules of a cycle when there is one.
+//!
+//! ```
+//! # use advent_of_code::order::Rules;
+//! let rules: Rules<u32> = [(47, 53), (97, 13), (97, 47), (75, 47), (53, 13)].into_iter().collect();
+//! assert!(rules.is_sorted(&[97, 75, 47, 53, 13]));
+//! assert!(!rules.is_sorted(&[53, 47]));
+//! assert_eq!(rules.topological_sort(&[13, 53, 97, 47]), Ok(vec![97, 47, 53, 13]));
+//!
+//! // with a rule between every pair of items, the rules also work as a comparator.
+//! let mut pages = vec![53, 47, 13];
+//! pages.sort_by(|a, b| rules.compare(a, b));
+//! assert_eq!(pages, vec![47, 53, 13]);
+//! ```
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BTreeSet, HashMap, HashSet},
+    error::Error,
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+/// Rules which make some items come before others.
+#[derive(Clone, Debug)]
+pub struct Rules<T> {
+    after: HashMap<T, HashSet<T>>,
+    rules: Vec<(T, T)>,
+    nodes: Vec<T>,
+}
+
+/// Rules forming a cycle, each one's `after` being the next one's `before`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<T> {
+    pub rules: Vec<(T, T)>,
+}
+
+impl<T: Display> Display for Cycle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cycle in rules")?;
+        for (i, (before, after)) in self.rules.iter().enumerate() {
+            let separator = if i == 0 { ": " } else { ", " };
+            write!(f, "{separator}{before}|{after}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Debug + Display> Error for Cycle<T> {}
+
+impl<T: Copy + Eq + Hash> Rules<T> {
+    pub fn new() -> Self {
+        Rules {
+            after: HashMap::new(),
+            rules: vec![],
+            nodes: vec![],
+        }
+    }
+
+    /// Adds a rule that `before` comes before `after`, if it is new.
+    pub fn add(&mut self, before: T, after: T) {
+        for node in [before, after] {
+            if let Entry::Vacant(entry) = self.after.entry(node) {
+                entry.insert(HashSet::new());
+                self.nodes.push(node);
+            }
+        }
+        if self.after.get_mut(&before).unwrap().insert(after) {
+            self.rules.push((before, after));
+        }
+    }
+
+    /// The number of rules.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The rules, in the order they were added.
+    pub fn rules(&self) -> &[(T, T)] {
+        &self.rules
+    }
+
+    /// The items appearing in the rules, in the order they first appeared.
+    pub fn nodes(&self) -> &[T] {
+        &self.nodes
+    }
+
+    /// Whether there is a rule that `before` comes before `after`.
+    pub fn precedes(&self, before: &T, after: &T) -> bool {
+        self.after
+            .get(before)
+            .is_some_and(|set| set.contains(after))
+    }
+
+    /// Compares two items by the rule between them, `Equal` if there is none. This is only a
+    /// valid order for [`slice::sort_by`] if there is a rule between any two items being sorted,
+    /// without a cycle; otherwise, use [`Rules::topological_sort`].
+    pub fn compare(&self, a: &T, b: &T) -> Ordering {
+        if self.precedes(a, b) {
+            Ordering::Less
+        } else if self.precedes(b, a) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// Whether no rule puts an item after one that follows it.
+    pub fn is_sorted(&self, items: &[T]) -> bool {
+        let positions: HashMap<T, usize> = items.iter().enumerate().map(|(i, x)| (*x, i)).collect();
+        items.iter().enumerate().all(|(i, item)| {
+            self.after.get(item).is_none_or(|set| {
+                set.iter()
+                    .all(|after| positions.get(after).is_none_or(|j| *j > i))
+            })
+        })
+    }
+
+    /// Sorts items so that they follow the rules between them, keeping the original order of
+    /// items when the rules allow it. Rules involving other items are ignored.
+    pub fn topological_sort(&self, items: &[T]) -> Result<Vec<T>, Cycle<T>> {
+        let positions: HashMap<T, usize> = items.iter().enumerate().map(|(i, x)| (*x, i)).collect();
+        let successors = |i: usize| {
+            self.after
+                .get(&items[i])
+                .into_iter()
+                .flatten()
+                .filter_map(|after| positions.get(after).copied())
+        };
+
+        let mut incoming = vec![0; items.len()];
+        for i in 0..items.len() {
+            for j in successors(i) {
+                incoming[j] += 1;
+            }
+        }
+
+        // Kahn's algorithm, taking the first item in the original order among the ready ones.
+        let mut ready: BTreeSet<usize> = (0..items.len()).filter(|i| incoming[*i] == 0).collect();
+        let mut sorted = Vec::with_capacity(items.len());
+        while let Some(i) = ready.pop_first() {
+            sorted.push(items[i]);
+            for j in successors(i) {
+                incoming[j] -= 1;
+                if incoming[j] == 0 {
+                    ready.insert(j);
+                }
+            }
+        }
+
+        if sorted.len() == items.len() {
+            Ok(sorted)
+        } else {
+            Err(self.find_cycle(items, &incoming))
+        }
+    }
+
+    /// A cycle among the items left with incoming rules, all of which come from other items left.
+    fn find_cycle(&self, items: &[T], incoming: &[usize]) -> Cycle<T> {
+        let left = |i: usize| incoming[i] > 0;
+        let predecessor = |j: usize| {
+            (0..items.len())
+                .find(|i| left(*i) && self.precedes(&items[*i], &items[j]))
+                .unwrap()
+        };
+
+        // going back through predecessors, an item is seen twice.
+        let mut seen = vec![None; items.len()];
+        let mut path = vec![(0..items.len()).find(|i| left(*i)).unwrap()];
+        loop {
+            let current = *path.last().unwrap();
+            if let Some(start) = seen[current] {
+                let mut cycle: Vec<usize> = path[start..].to_vec();
+                cycle.reverse();
+                let rules = cycle
+                    .windows(2)
+                    .map(|w| (items[w[0]], items[w[1]]))
+                    .collect();
+                return Cycle { rules };
+            }
+            seen[current] = Some(path.len() - 1);
+            path.push(predecessor(current));
+        }
+    }
+}
+
+impl<T: Copy + Eq + Hash> Default for Rules<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy + Eq + Hash> FromIterator<(T, T)> for Rules<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut rules = Rules::new();
+        for (before, after) in iter {
+            rules.add(before, after);
+        }
+        rules
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_order() {
+        let rules: Rules<char> = [('a', 'b'), ('b', 'c'), ('a', 'b')].into_iter().collect();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules.nodes(), &['a', 'b', 'c']);
+        assert!(rules.is_sorted(&['a', 'b', 'c']));
+        // only direct rules count.
+        assert!(rules.is_sorted(&['c', 'a']));
+        assert!(!rules.is_sorted(&['a', 'c', 'b']));
+        assert!(rules.is_sorted(&['x', 'b', 'y']));
+        assert_eq!(rules.compare(&'b', &'a'), Ordering::Greater);
+        assert_eq!(rules.compare(&'a', &'c'), Ordering::Equal);
+    }
+
+    #[test]
+    fn sorts_subsets() {
+        // consistent within any three items, but a cycle as a whole.
+        let rules: Rules<u8> = [(1, 2), (2, 3), (3, 4), (4, 1)].into_iter().collect();
+        assert_eq!(rules.topological_sort(&[3, 1, 2]), Ok(vec![1, 2, 3]));
+        assert_eq!(rules.topological_sort(&[1, 4, 3]), Ok(vec![3, 4, 1]));
+        // unrelated items keep their order.
+        assert_eq!(rules.topological_sort(&[9, 2, 8, 1]), Ok(vec![9, 8, 1, 2]));
+        assert_eq!(rules.topological_sort(&[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let rules: Rules<u8> = [(1, 2), (2, 3), (3, 1), (3, 4), (5, 1)]
+            .into_iter()
+            .collect();
+        let cycle = rules.topological_sort(&[5, 4, 3, 2, 1]).unwrap_err();
+
+        assert_eq!(cycle.rules.len(), 3);
+        for (i, (before, after)) in cycle.rules.iter().enumerate() {
+            assert!(rules.precedes(before, after));
+            assert_eq!(cycle.rules[(i + 1) % 3].0, *after);
+        }
+        let error = Cycle {
+            rules: vec![(1, 2), (2, 1)],
+        };
+        assert_eq!(error.to_string(), "cycle in rules: 1|2, 2|1");
+    }
+}
@@ -0,0 +1,304 @@
+//! Helpers to parse puzzle inputs, with errors that tell where the input is wrong.
+//!
+//! ```
+//! # use advent_of_code::parse;
+//! let [px, py, vx, vy] = parse::signed_n::<i64, 4>("p=0,4 v=3,-3").unwrap();
+//! assert_eq!((px, py, vx, vy), (0, 4, 3, -3));
+//!
+//! let a: u64 = parse::field("Register A: 729", "Register A", parse::number).unwrap();
+//! assert_eq!(a, 729);
+//!
+//! let error = parse::field::<Vec<u8>>("Program: 0,x", "Program", parse::list).unwrap_err();
+//! assert_eq!(error.to_string(), "line 1, column 12: invalid number \"x\": invalid digit found in string");
+//! ```
+use std::{error::Error, fmt::Display, str::FromStr};
+
+/// Why a text could not be parsed. Lines and columns start at 1, and columns count characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error at a byte offset of a text.
+    pub fn at(text: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    /// Moves an error found in a part of a text, starting at a byte offset, to its position in
+    /// the whole text.
+    pub fn within(self, text: &str, start: usize) -> Self {
+        let origin = ParseError::at(text, start, "");
+        ParseError {
+            line: origin.line + self.line - 1,
+            column: if self.line == 1 {
+                origin.column + self.column - 1
+            } else {
+                self.column
+            },
+            message: self.message,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Byte offset of `part` in `text`, which it must be a slice of.
+fn offset_in(text: &str, part: &str) -> usize {
+    part.as_ptr() as usize - text.as_ptr() as usize
+}
+
+fn integers<T: FromStr>(text: &str, signed: bool) -> Result<Vec<T>, ParseError>
+where
+    T::Err: Display,
+{
+    let bytes = text.as_bytes();
+    let mut numbers = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let negative =
+            signed && bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+        if !negative && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += usize::from(negative);
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        let number = &text[start..i];
+        numbers.push(
+            number.parse().map_err(|e| {
+                ParseError::at(text, start, format!("invalid number {number:?}: {e}"))
+            })?,
+        );
+    }
+
+    Ok(numbers)
+}
+
+/// All the numbers in a text, ignoring everything else, including minus signs.
+pub fn unsigned<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError>
+where
+    T::Err: Display,
+{
+    integers(text, false)
+}
+
+/// All the numbers in a text, ignoring everything else. A `-` right before a number makes it
+/// negative.
+pub fn signed<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError>
+where
+    T::Err: Display,
+{
+    integers(text, true)
+}
+
+fn exactly<T, const N: usize>(text: &str, numbers: Vec<T>) -> Result<[T; N], ParseError> {
+    let found = numbers.len();
+    numbers.try_into().map_err(|_| {
+        ParseError::at(
+            text,
+            text.len(),
+            format!("expected {N} numbers, found {found}"),
+        )
+    })
+}
+
+/// Like [`unsigned`], for a text with exactly `N` numbers.
+pub fn unsigned_n<T: FromStr, const N: usize>(text: &str) -> Result<[T; N], ParseError>
+where
+    T::Err: Display,
+{
+    exactly(text, unsigned(text)?)
+}
+
+/// Like [`signed`], for a text with exactly `N` numbers.
+pub fn signed_n<T: FromStr, const N: usize>(text: &str) -> Result<[T; N], ParseError>
+where
+    T::Err: Display,
+{
+    exactly(text, signed(text)?)
+}
+
+/// A single number, with surrounding whitespace.
+pub fn number<T: FromStr>(text: &str) -> Result<T, ParseError>
+where
+    T::Err: Display,
+{
+    let trimmed = text.trim();
+    trimmed.parse().map_err(|e| {
+        ParseError::at(
+            text,
+            offset_in(text, trimmed),
+            format!("invalid number {trimmed:?}: {e}"),
+        )
+    })
+}
+
+/// Comma-separated values, with whitespace around them.
+pub fn list<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError>
+where
+    T::Err: Display,
+{
+    text.split(',')
+        .map(|item| number(item).map_err(|e| e.within(text, offset_in(text, item))))
+        .collect()
+}
+
+/// Splits a `key: value` line, trimming both sides.
+pub fn key_value(line: &str) -> Result<(&str, &str), ParseError> {
+    line.split_once(':')
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .ok_or_else(|| ParseError::at(line, 0, "expected `key: value`"))
+}
+
+/// Parses the value of a `key: value` line with `parse`, checking the key.
+pub fn field<T>(
+    line: &str,
+    key: &str,
+    parse: impl FnOnce(&str) -> Result<T, ParseError>,
+) -> Result<T, ParseError> {
+    let (found, value) = key_value(line)?;
+    if found != key {
+        return Err(ParseError::at(
+            line,
+            offset_in(line, found),
+            format!("expected `{key}`, found `{found}`"),
+        ));
+    }
+    parse(value).map_err(|e| e.within(line, offset_in(line, value)))
+}
+
+/// Blocks of lines separated by blank lines, without their trailing line break.
+pub fn blocks(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        // skip the blank lines before the block.
+        while let Some(line) = rest.split_inclusive('\n').next() {
+            if !line.trim().is_empty() {
+                break;
+            }
+            rest = &rest[line.len()..];
+        }
+        if rest.is_empty() {
+            return None;
+        }
+
+        let mut end = 0;
+        for line in rest.split_inclusive('\n') {
+            if line.trim().is_empty() {
+                break;
+            }
+            end += line.len();
+        }
+        let block = &rest[..end];
+        rest = &rest[end..];
+        Some(block.trim_end_matches(['\n', '\r']))
+    })
+}
+
+/// Parses each line with `parse`, reporting errors at their position in the text.
+pub fn each_line<T>(
+    text: &str,
+    mut parse: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    text.lines()
+        .map(|line| parse(line).map_err(|e| e.within(text, offset_in(text, line))))
+        .collect()
+}
+
+/// Parses each block of [`blocks`] with `parse`, reporting errors at their position in the text.
+pub fn each_block<T>(
+    text: &str,
+    mut parse: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    blocks(text)
+        .map(|block| parse(block).map_err(|e| e.within(text, offset_in(text, block))))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_numbers() {
+        assert_eq!(unsigned::<u32>("47|53"), Ok(vec![47, 53]));
+        assert_eq!(unsigned::<u32>("v=3,-3"), Ok(vec![3, 3]));
+        assert_eq!(signed::<i32>("v=3,-3 a-b"), Ok(vec![3, -3]));
+        assert_eq!(signed_n::<i32, 2>("x-1 y2"), Ok([-1, 2]));
+        assert_eq!(unsigned::<u8>("none"), Ok(vec![]));
+    }
+
+    #[test]
+    fn locates_errors() {
+        assert_eq!(
+            unsigned::<u8>("1\n2 300"),
+            Err(ParseError {
+                line: 2,
+                column: 3,
+                message: "invalid number \"300\": number too large to fit in target type".into()
+            })
+        );
+
+        let error = unsigned_n::<u8, 3>("1, 2").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+        assert_eq!(error.message, "expected 3 numbers, found 2");
+
+        let error = each_line("a: 1\nb: 2\nc 3", |l| field(l, "c", number::<u8>)).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.message, "expected `c`, found `a`");
+
+        let error = each_line("a: 1\na: x", |l| field(l, "a", number::<u8>)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+    }
+
+    #[test]
+    fn parses_lists_and_fields() {
+        assert_eq!(list::<u8>("0, 1,5"), Ok(vec![0, 1, 5]));
+        assert_eq!(key_value("Register A:  729 "), Ok(("Register A", "729")));
+        assert!(key_value("Register A").is_err());
+
+        let error = field::<Vec<u8>>("Program: 0,1,,3", "Program", list).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 14));
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let text = "a\nb\n\n \nc\r\n\r\nd\n";
+        assert_eq!(blocks(text).collect::<Vec<_>>(), vec!["a\nb", "c", "d"]);
+        assert_eq!(blocks("").count(), 0);
+
+        let error = each_block(text, |block| {
+            each_line(block, |line| match line {
+                "d" => Err(ParseError::at(line, 0, "no d")),
+                _ => Ok(()),
+            })
+        })
+        .unwrap_err();
+        assert_eq!((error.line, error.column), (7, 1));
+    }
+}
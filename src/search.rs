@@ -0,0 +1,389 @@
+//! Shortest paths in graphs of states, given by a function returning the successors of a state.
+//!
+//! Searches keep every predecessor of a state on a shortest path, not just one, so that all the
+//! shortest paths can be followed back from the goal, and stop once no cheaper goal can be found.
+//!
+//! ```
+//! # use advent_of_code::search;
+//! // two ways from 0 to 3, both of cost 2.
+//! let edges = |n: &u8| match n {
+//!     0 => vec![(1, 1), (2, 1)],
+//!     1 | 2 => vec![(3, 1)],
+//!     _ => vec![],
+//! };
+//! let result = search::dijkstra([0], edges, |n| *n == 3);
+//! assert_eq!(result.goal_cost(), Some(2));
+//! assert_eq!(result.path(), Some(vec![0, 1, 3]));
+//! assert_eq!(result.on_best_paths().len(), 4);
+//! ```
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use crate::geometry::{Direction, Position};
+
+/// Costs of moves, with [`Default`] as zero.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+/// What a search found: the cost of the states it reached, and the predecessors on their
+/// shortest paths.
+#[derive(Clone, Debug)]
+pub struct Search<S, C> {
+    costs: HashMap<S, C>,
+    predecessors: HashMap<S, Vec<S>>,
+    starts: HashSet<S>,
+    goals: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Cost> Search<S, C> {
+    fn new() -> Self {
+        Search {
+            costs: HashMap::new(),
+            predecessors: HashMap::new(),
+            starts: HashSet::new(),
+            goals: vec![],
+        }
+    }
+
+    fn start(&mut self, state: S) {
+        self.costs.insert(state.clone(), C::default());
+        self.starts.insert(state);
+    }
+
+    /// Records a move to `to` at `cost`, returning whether it is worth exploring from there.
+    ///
+    /// Starts never get predecessors, even through moves of zero cost, so that paths followed
+    /// back from a state always end on a start.
+    fn relax(&mut self, from: &S, to: S, cost: C) -> bool {
+        if self.starts.contains(&to) {
+            return false;
+        }
+        match self.costs.get(&to) {
+            Some(known) if *known < cost => false,
+            Some(known) if *known == cost => {
+                let predecessors = self.predecessors.entry(to).or_default();
+                if !predecessors.contains(from) {
+                    predecessors.push(from.clone());
+                }
+                false
+            }
+            _ => {
+                self.costs.insert(to.clone(), cost);
+                self.predecessors.insert(to, vec![from.clone()]);
+                true
+            }
+        }
+    }
+
+    /// The cost of the shortest path to a state, if it was reached.
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    /// The goals reached, all at the cost of the shortest path to a goal.
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+
+    pub fn goal_cost(&self) -> Option<C> {
+        self.cost(self.goals.first()?)
+    }
+
+    /// The states just before a state on its shortest paths.
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// One of the shortest paths to a state, from the start it is closest to.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.costs.get(state)?;
+
+        let mut path = vec![state.clone()];
+        while let Some(previous) = self.predecessors(path.last().unwrap()).first() {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// One of the shortest paths to a goal.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goals.first()?)
+    }
+
+    /// The edges of all the shortest paths to the goals, as the predecessors of each state on
+    /// them.
+    pub fn best_paths_dag(&self) -> HashMap<S, Vec<S>> {
+        let mut dag = HashMap::new();
+        let mut stack = self.goals.clone();
+
+        while let Some(state) = stack.pop() {
+            if dag.contains_key(&state) {
+                continue;
+            }
+            let predecessors = self.predecessors(&state).to_vec();
+            stack.extend(predecessors.iter().cloned());
+            dag.insert(state, predecessors);
+        }
+
+        dag
+    }
+
+    /// The states on any of the shortest paths to the goals.
+    pub fn on_best_paths(&self) -> HashSet<S> {
+        self.best_paths_dag().into_keys().collect()
+    }
+}
+
+/// Breadth-first search, where every move costs 1.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        search.start(start.clone());
+        queue.push_back((start, 0));
+    }
+
+    while let Some((state, cost)) = queue.pop_front() {
+        if search.goal_cost().is_some_and(|best| cost > best) {
+            break;
+        }
+        if is_goal(&state) {
+            search.goals.push(state);
+            continue;
+        }
+
+        for next in successors(&state) {
+            if search.relax(&state, next.clone(), cost + 1) {
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    search
+}
+
+/// A state waiting to be explored, ordered so that [`BinaryHeap`] pops the lowest estimate first.
+struct Entry<S, C> {
+    estimate: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // on equal estimates, the most advanced state first.
+        (other.estimate.cmp(&self.estimate)).then(self.cost.cmp(&other.cost))
+    }
+}
+
+/// A* search. `heuristic` must never overestimate the cost to a goal, and must not decrease by
+/// more than the cost of a move, for the costs to be the shortest.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        search.start(start.clone());
+        heap.push(Entry {
+            estimate: heuristic(&start),
+            cost: C::default(),
+            state: start,
+        });
+    }
+
+    while let Some(Entry {
+        estimate,
+        cost,
+        state,
+    }) = heap.pop()
+    {
+        if search.goal_cost().is_some_and(|best| estimate > best) {
+            break;
+        }
+        // a cheaper way to this state was found after it was queued.
+        if search.cost(&state).is_some_and(|known| known < cost) {
+            continue;
+        }
+        if is_goal(&state) {
+            if !search.goals.contains(&state) {
+                search.goals.push(state);
+            }
+            continue;
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if search.relax(&state, next.clone(), next_cost) {
+                heap.push(Entry {
+                    estimate: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    state: next,
+                });
+            }
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm: [`astar`] without a heuristic.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// Moves of a walker facing a direction on a grid of size `(width, height)`: a step forward for
+/// `step`, or a quarter turn in place for `turn`. `is_open` tells which positions can be walked
+/// on.
+pub fn walker_moves<C: Cost>(
+    (position, direction): (Position, Direction),
+    size: (usize, usize),
+    step: C,
+    turn: C,
+    mut is_open: impl FnMut(Position) -> bool,
+) -> impl Iterator<Item = ((Position, Direction), C)> {
+    let forward = position
+        .step(direction, size)
+        .filter(|next| is_open(*next))
+        .map(|next| ((next, direction), step));
+    let turns = [direction.turn_left(), direction.turn_right()].map(|d| ((position, d), turn));
+    forward.into_iter().chain(turns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A grid of `#` walls and open cells, with `S` and `E`.
+    const MAZE: &str = "\
+#######
+#....E#
+#.#.#.#
+#S....#
+#######";
+
+    fn maze() -> (crate::grid::Grid<char>, Position, Position) {
+        let grid = crate::grid::Grid::<char>::parse(MAZE).unwrap();
+        let start = Position::from(grid.find(&'S').unwrap());
+        let end = Position::from(grid.find(&'E').unwrap());
+        (grid, start, end)
+    }
+
+    #[test]
+    fn finds_all_shortest_paths_with_bfs() {
+        let (grid, start, end) = maze();
+        let result = bfs(
+            [start],
+            |p| p.neighbours(grid.size()).filter(|n| grid[*n] != '#'),
+            |p| *p == end,
+        );
+
+        assert_eq!(result.goal_cost(), Some(6));
+        assert_eq!(result.path().map(|p| p.len()), Some(7));
+        // every open cell is on a shortest path, going up and right.
+        assert_eq!(result.on_best_paths().len(), 13);
+        assert_eq!(result.predecessors(&end).len(), 2);
+    }
+
+    #[test]
+    fn accounts_for_turns() {
+        let (grid, start, end) = maze();
+        let result = dijkstra(
+            [(start, Direction::Right)],
+            |&state| walker_moves(state, grid.size(), 1, 1000, |p| grid[p] != '#'),
+            |(p, _)| *p == end,
+        );
+
+        // along the bottom then up, as going up first needs a second turn at the top.
+        assert_eq!(result.goal_cost(), Some(1006));
+        assert_eq!(result.goals(), &[(end, Direction::Up)]);
+        let tiles: HashSet<Position> = result.on_best_paths().into_iter().map(|s| s.0).collect();
+        assert_eq!(tiles.len(), 7);
+        assert_eq!(
+            result.path().unwrap().first(),
+            Some(&(start, Direction::Right))
+        );
+    }
+
+    #[test]
+    fn guides_with_heuristic() {
+        let mut expanded = 0;
+        let result = astar(
+            [0],
+            |n: &i32| {
+                expanded += 1;
+                [(n - 1, 1), (n + 1, 1)]
+            },
+            |n| n.abs_diff(10),
+            |n| *n == 10,
+        );
+
+        assert_eq!(result.goal_cost(), Some(10));
+        assert_eq!(expanded, 10);
+        assert_eq!(result.cost(&-2), None);
+    }
+
+    #[test]
+    fn stops_without_goal() {
+        let result: Search<u8, u8> = dijkstra([0], |n| (*n < 5).then_some((n + 1, 2)), |_| false);
+        assert_eq!(result.goal_cost(), None);
+        assert_eq!(result.cost(&5), Some(10));
+        assert_eq!(result.path_to(&2), Some(vec![0, 1, 2]));
+        assert_eq!(result.path_to(&6), None);
+    }
+
+    #[test]
+    fn ignores_free_moves_back_to_start() {
+        // a cycle 0 → 1 → 2 → 0 where every move is free.
+        let result: Search<u8, u8> = dijkstra([0], |n| [((n + 1) % 3, 0)], |_| false);
+        assert_eq!(result.cost(&2), Some(0));
+        assert_eq!(result.predecessors(&0), &[]);
+        assert_eq!(result.path_to(&0), Some(vec![0]));
+        assert_eq!(result.path_to(&2), Some(vec![0, 1, 2]));
+    }
+}
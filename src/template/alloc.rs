@@ -0,0 +1,180 @@
+/// A global allocator that counts allocations, so heap usage can be reported next to timings
+/// without switching to the `dhat` profile. Installed by `solution!` with the `alloc-stats` feature.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES_ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+static BASELINE: AtomicU64 = AtomicU64::new(0);
+
+/// Wraps the system allocator and keeps track of allocated bytes.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // a reallocation counts as a new allocation of `new_size` bytes.
+            CURRENT.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    let size = size as u64;
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES_ALLOCATED.fetch_add(size, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+/// Resets the counters. Memory that is live at this point is not counted towards the peak.
+pub fn reset() {
+    let current = CURRENT.load(Ordering::Relaxed);
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    BYTES_ALLOCATED.store(0, Ordering::Relaxed);
+    BASELINE.store(current, Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+}
+
+/// Returns the statistics since the last call to [`reset`].
+pub fn snapshot() -> AllocStats {
+    AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES_ALLOCATED.load(Ordering::Relaxed),
+        peak: PEAK
+            .load(Ordering::Relaxed)
+            .saturating_sub(BASELINE.load(Ordering::Relaxed)),
+    }
+}
+
+/// Heap usage of a solution part.
+///
+/// # Display
+/// This value displays as `12 allocs, 3.4KiB total, 2.1KiB peak`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations.
+    pub allocations: u64,
+    /// Total number of bytes allocated.
+    pub bytes: u64,
+    /// Maximum number of bytes live at the same time.
+    pub peak: u64,
+}
+
+impl AllocStats {
+    /// Parses the [`Display`] representation. Byte counts are approximate due to unit rounding.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.split(',').map(str::trim);
+
+        let allocations = parts.next()?.strip_suffix(" allocs")?.parse().ok()?;
+        let bytes = parse_bytes(parts.next()?.strip_suffix(" total")?)?;
+        let peak = parse_bytes(parts.next()?.strip_suffix(" peak")?)?;
+
+        Some(AllocStats {
+            allocations,
+            bytes,
+            peak,
+        })
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} total, {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+/// Formats a byte count with a binary unit, e.g. `3.4KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes}B")
+    } else {
+        format!("{value:.1}{}", UNITS[unit])
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_bytes(s: &str) -> Option<u64> {
+    // iterate from the largest unit, since every unit ends with `B`.
+    UNITS.iter().enumerate().rev().find_map(|(i, unit)| {
+        let value: f64 = s.strip_suffix(unit)?.parse().ok()?;
+        Some((value * 1024_f64.powi(i32::try_from(i).ok()?)).round() as u64)
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, AllocStats};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0B");
+        assert_eq!(format_bytes(1023), "1023B");
+        assert_eq!(format_bytes(3482), "3.4KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0MiB");
+    }
+
+    #[test]
+    fn roundtrips_stats() {
+        let stats = AllocStats {
+            allocations: 12,
+            bytes: 512,
+            peak: 5 * 1024 * 1024,
+        };
+        let s = stats.to_string();
+        assert_eq!(s, "12 allocs, 512B total, 5.0MiB peak");
+        assert_eq!(AllocStats::parse(&s), Some(stats));
+    }
+
+    #[test]
+    fn rejects_malformed_stats() {
+        assert_eq!(AllocStats::parse("12 allocs"), None);
+        assert_eq!(AllocStats::parse("a allocs, 1B total, 1B peak"), None);
+    }
+}
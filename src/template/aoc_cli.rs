@@ -1,7 +1,9 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
+    env,
     fmt::Display,
-    process::{Command, Output, Stdio},
+    fs,
+    process::{self, Command, Output, Stdio},
 };
 
 use crate::template::Day;
@@ -11,6 +13,8 @@ pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    /// The downloaded input of a day differs from the existing one.
+    InputChanged(Day),
 }
 
 impl Display for AocCommandError {
@@ -21,12 +25,24 @@ impl Display for AocCommandError {
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::InputChanged(day) => write!(
+                f,
+                "the downloaded input differs from \"{}\", kept it and saved the download to \"{}\".",
+                get_input_path(*day),
+                changed_input_path(*day)
+            ),
         }
     }
 }
 
+/// The aoc-cli command. The `AOC_CLI` environment variable can point to another executable,
+/// which the end-to-end tests use to run against a mock of aoc-cli.
+fn aoc_command() -> Command {
+    Command::new(env::var("AOC_CLI").unwrap_or_else(|_| "aoc".into()))
+}
+
 pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
+    aoc_command()
         .arg("-V")
         .output()
         .map_err(|_| AocCommandError::CommandNotFound)?;
@@ -49,26 +65,124 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+/// Which files of a day to download.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DownloadMode {
+    Puzzle,
+    Input,
+    Both,
+}
 
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
+impl DownloadMode {
+    fn includes_puzzle(self) -> bool {
+        self != DownloadMode::Input
+    }
+
+    fn includes_input(self) -> bool {
+        self != DownloadMode::Puzzle
+    }
+}
+
+/// What happened to an existing file when a new version was downloaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileStatus {
+    Created,
+    Unchanged,
+    Updated,
+    /// The file differs from the download and was kept, see [`changed_input_path`].
+    Kept,
+}
 
+/// Compares a downloaded file to the existing one at `path`, and writes it unless it is unchanged.
+/// Trailing whitespace is ignored, editors tend to add or strip final newlines.
+///
+/// When `replace` is false, a changed file is kept and the download is saved next to it instead.
+fn store(path: &str, downloaded: &[u8], replace: bool) -> std::io::Result<FileStatus> {
+    // `scaffold` creates empty inputs, which are placeholders rather than previous downloads.
+    let existing = fs::read(path).unwrap_or_default();
+    if existing.trim_ascii().is_empty() {
+        fs::write(path, downloaded)?;
+        return Ok(FileStatus::Created);
+    }
+
+    if existing.trim_ascii_end() == downloaded.trim_ascii_end() {
+        Ok(FileStatus::Unchanged)
+    } else if replace {
+        fs::write(path, downloaded)?;
+        Ok(FileStatus::Updated)
+    } else {
+        fs::write(format!("{path}.new"), downloaded)?;
+        Ok(FileStatus::Kept)
+    }
+}
+
+/// Where a download is saved when it differs from the existing input, e.g. `data/inputs/01.txt.new`.
+pub fn changed_input_path(day: Day) -> String {
+    format!("{}.new", get_input_path(day))
+}
+
+/// Downloads the files of a day to a temporary location, then stores those that changed.
+///
+/// The description is replaced when it changed, which happens once part one is solved. The input never
+/// should, so a changed input is kept and reported with [`AocCommandError::InputChanged`].
+pub fn download(day: Day, mode: DownloadMode) -> Result<Output, AocCommandError> {
+    let tmp_dir = env::temp_dir();
+    let tmp_path = |kind: &str| {
+        tmp_dir
+            .join(format!("aoc-{}-{day}-{kind}", process::id()))
+            .to_string_lossy()
+            .into_owned()
+    };
+    let (tmp_input_path, tmp_puzzle_path) = (tmp_path("input.txt"), tmp_path("puzzle.md"));
+
+    let mut download_args = vec!["--overwrite".to_string()];
+    match mode {
+        DownloadMode::Puzzle => download_args.push("--puzzle-only".into()),
+        DownloadMode::Input => download_args.push("--input-only".into()),
+        DownloadMode::Both => {}
+    }
+    download_args.extend([
+        "--input-file".into(),
+        tmp_input_path.clone(),
+        "--puzzle-file".into(),
+        tmp_puzzle_path.clone(),
+    ]);
+
+    let args = build_args("download", &download_args, day);
     let output = call_aoc_cli(&args)?;
     println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+
+    let mut files = vec![];
+    if mode.includes_puzzle() {
+        files.push(("puzzle", get_puzzle_path(day), tmp_puzzle_path, true));
+    }
+    if mode.includes_input() {
+        files.push(("input", get_input_path(day), tmp_input_path, false));
+    }
+
+    let mut input_changed = false;
+
+    for (kind, path, tmp_path, replace) in files {
+        let downloaded =
+            fs::read(&tmp_path).map_err(|_| AocCommandError::BadExitStatus(output.clone()))?;
+        let _ = fs::remove_file(&tmp_path);
+
+        match store(&path, &downloaded, replace) {
+            Ok(FileStatus::Created) => println!("🎄 Successfully wrote {kind} to \"{path}\"."),
+            Ok(FileStatus::Updated) => println!("🎄 Successfully updated {kind} \"{path}\"."),
+            Ok(FileStatus::Unchanged) => println!("🎄 {kind} \"{path}\" is up to date."),
+            Ok(FileStatus::Kept) => input_changed = true,
+            Err(e) => {
+                eprintln!("failed to write {kind} to \"{path}\": {e}");
+                return Err(AocCommandError::BadExitStatus(output));
+            }
+        }
+    }
+
+    if input_changed {
+        return Err(AocCommandError::InputChanged(day));
+    }
+
     Ok(output)
 }
 
@@ -77,18 +191,22 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    // the server response is captured so the verdict can be recorded, then echoed.
+    let output = call_aoc_cli_captured(&args)?;
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    Ok(output)
 }
 
-fn get_input_path(day: Day) -> String {
+pub fn get_input_path(day: Day) -> String {
     format!("data/inputs/{day}.txt")
 }
 
-fn get_puzzle_path(day: Day) -> String {
+pub fn get_puzzle_path(day: Day) -> String {
     format!("data/puzzles/{day}.md")
 }
 
-fn get_year() -> Option<u16> {
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
@@ -110,7 +228,7 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
 
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
+    let output = aoc_command()
         .args(args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
@@ -123,3 +241,52 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+fn call_aoc_cli_captured(args: &[String]) -> Result<Output, AocCommandError> {
+    let output = aoc_command()
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        print!("{}", String::from_utf8_lossy(&output.stdout));
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{store, FileStatus};
+
+    #[test]
+    fn stores_changed_files() {
+        let path = env::temp_dir()
+            .join(format!("aoc-store-test-{}.txt", process::id()))
+            .to_string_lossy()
+            .into_owned();
+        let new_path = format!("{path}.new");
+
+        fs::write(&path, "").unwrap();
+        assert_eq!(store(&path, b"1\n2", false).unwrap(), FileStatus::Created);
+        assert_eq!(
+            store(&path, b"1\n2\n", false).unwrap(),
+            FileStatus::Unchanged
+        );
+
+        assert_eq!(store(&path, b"1\n3\n", false).unwrap(), FileStatus::Kept);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2");
+        assert_eq!(fs::read_to_string(&new_path).unwrap(), "1\n3\n");
+
+        assert_eq!(store(&path, b"1\n3\n", true).unwrap(), FileStatus::Updated);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n3\n");
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&new_path);
+    }
+}
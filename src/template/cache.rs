@@ -0,0 +1,301 @@
+/// Results of previous runs, keyed by a hash of everything that can change them: the source of
+/// the day, the shared library code, the input and the build profile.
+use std::{collections::HashMap, fs, io::Error, path::Path, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static CACHE_FILE_PATH: &str = "./data/cache.json";
+
+/// 64-bit FNV-1a, which is stable across toolchains unlike the hasher of the standard library.
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+
+    /// Hashes the length first, so consecutive writes can't be confused with each other.
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in (bytes.len() as u64).to_le_bytes().iter().chain(bytes) {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+fn collect_sources(dir: &Path, files: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            // solutions are hashed separately, each day only depends on its own.
+            if !path.ends_with("bin") {
+                collect_sources(&path, files);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "rs") && !path.ends_with("main.rs") {
+            files.push(path.to_string_lossy().into_owned());
+        }
+    }
+}
+
+/// Computes cache keys for the days of a run.
+pub struct KeyBuilder {
+    /// Hash of the manifest and the library code shared by all days.
+    shared: u64,
+    profile: String,
+}
+
+impl KeyBuilder {
+    /// `profile` describes how solutions are built and run, e.g. `release`.
+    pub fn new(profile: &str) -> Self {
+        let mut files = vec!["Cargo.toml".to_string()];
+        collect_sources(Path::new("src"), &mut files);
+        files.sort();
+
+        let mut hasher = Fnv::new();
+        for file in files {
+            hasher.write(file.as_bytes());
+            hasher.write(&fs::read(&file).unwrap_or_default());
+        }
+
+        KeyBuilder {
+            shared: hasher.0,
+            profile: profile.into(),
+        }
+    }
+
+    /// The key of a day, or `None` if the day has not been scaffolded.
+    pub fn key(&self, day: Day) -> Option<String> {
+        let source = fs::read(format!("src/bin/{day}.rs")).ok()?;
+        // a missing input is hashed as empty, so the day reruns once it is downloaded.
+        let input = fs::read(format!("data/inputs/{day}.txt")).unwrap_or_default();
+
+        let mut hasher = Fnv::new();
+        hasher.write(&self.shared.to_le_bytes());
+        hasher.write(self.profile.as_bytes());
+        hasher.write(&source);
+        hasher.write(&input);
+
+        Some(format!("{:016x}", hasher.0))
+    }
+}
+
+/// The result of a day, as printed by its solution bin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CacheEntry {
+    pub day: Day,
+    pub key: String,
+    pub answers: [Option<String>; 2],
+    /// Output lines of the run, including timings.
+    pub output: Vec<String>,
+}
+
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct ResultCache {
+    pub data: Vec<CacheEntry>,
+}
+
+impl ResultCache {
+    /// Dehydrate the cache to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(CACHE_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the cache from a JSON file. If not present or outdated, returns an empty cache.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(CACHE_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(ResultCache::try_from)
+            .unwrap_or_default()
+    }
+
+    /// The cached result of a day, if its key matches.
+    pub fn get(&self, day: Day, key: &str) -> Option<&CacheEntry> {
+        self.data.iter().find(|e| e.day == day && e.key == key)
+    }
+
+    /// Stores the result of a day, replacing the previous one.
+    pub fn insert(&mut self, entry: CacheEntry) {
+        self.data.retain(|e| e.day != entry.day);
+        self.data.push(entry);
+        self.data.sort_unstable_by_key(|e| e.day);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<ResultCache> for JsonValue {
+    fn from(value: ResultCache) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for ResultCache {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(ResultCache {
+            data: json_data
+                .iter()
+                .map(CacheEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&CacheEntry> for JsonValue {
+    fn from(value: &CacheEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("key".into(), JsonValue::String(value.key.clone()));
+        map.insert(
+            "answers".into(),
+            JsonValue::Array(
+                value
+                    .answers
+                    .iter()
+                    .map(|a| a.clone().map_or(JsonValue::Null, JsonValue::String))
+                    .collect(),
+            ),
+        );
+        map.insert(
+            "output".into(),
+            JsonValue::Array(
+                value
+                    .output
+                    .iter()
+                    .map(|l| JsonValue::String(l.clone()))
+                    .collect(),
+            ),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for CacheEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected cache entry to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected cache_entry.day to be a Day struct.")?;
+
+        let key = json
+            .get("key")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected cache_entry.key to be a string.")?;
+
+        let answers = json
+            .get("answers")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .filter(|a| a.len() == 2)
+            .ok_or("Expected cache_entry.answers to be an array of two answers.")?;
+        let answer = |v: &JsonValue| v.get::<String>().cloned();
+
+        let output = json
+            .get("output")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .and_then(|lines| {
+                lines
+                    .iter()
+                    .map(|l| l.get::<String>().cloned())
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or("Expected cache_entry.output to be an array of strings.")?;
+
+        Ok(CacheEntry {
+            day,
+            key: key.clone(),
+            answers: [answer(&answers[0]), answer(&answers[1])],
+            output,
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{CacheEntry, Fnv, ResultCache};
+    use crate::{day, template::Day};
+
+    fn entry(day: Day, key: &str) -> CacheEntry {
+        CacheEntry {
+            day,
+            key: key.into(),
+            answers: [Some("42".into()), None],
+            output: vec!["Part 1: 42 (1.0ms)".into(), "Part 2: ✖".into()],
+        }
+    }
+
+    fn hash(parts: &[&[u8]]) -> u64 {
+        let mut hasher = Fnv::new();
+        for part in parts {
+            hasher.write(part);
+        }
+        hasher.0
+    }
+
+    #[test]
+    fn separates_writes() {
+        assert_eq!(hash(&[b"a", b"b"]), hash(&[b"a", b"b"]));
+        assert_ne!(hash(&[b"ab", b""]), hash(&[b"a", b"b"]));
+        assert_ne!(hash(&[b""]), hash(&[]));
+    }
+
+    #[test]
+    fn looks_up_matching_keys() {
+        let mut cache = ResultCache::default();
+        cache.insert(entry(day!(2), "abc"));
+        cache.insert(entry(day!(1), "def"));
+        cache.insert(entry(day!(2), "ghi"));
+
+        assert_eq!(cache.data.len(), 2);
+        assert_eq!(cache.data[0].day, day!(1));
+        assert!(cache.get(day!(2), "abc").is_none());
+        assert_eq!(cache.get(day!(2), "ghi"), Some(&entry(day!(2), "ghi")));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let cache = ResultCache {
+            data: vec![entry(day!(1), "abc")],
+        };
+        let json = tinyjson::JsonValue::from(cache).stringify().unwrap();
+        let parsed = ResultCache::try_from(json).unwrap();
+        assert_eq!(parsed.data, vec![entry(day!(1), "abc")]);
+    }
+}
@@ -1,5 +1,18 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::all_days;
+use crate::template::run_multi::{run_multi, CacheMode};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(is_release: bool, jobs: usize, force: bool) {
+    let cache_mode = if force {
+        CacheMode::Refresh
+    } else {
+        CacheMode::Use
+    };
+    run_multi(
+        &all_days().collect(),
+        is_release,
+        false,
+        false,
+        jobs,
+        cache_mode,
+    );
 }
@@ -1,14 +1,22 @@
-use crate::template::{aoc_cli, Day};
+use crate::template::aoc_cli::{self, AocCommandError, DownloadMode};
+use crate::template::{Day, ANSI_RESET, ANSI_YELLOW};
 use std::process;
 
-pub fn handle(day: Day) {
+pub fn handle(day: Day, mode: DownloadMode) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    match aoc_cli::download(day, mode) {
+        Ok(_) => {}
+        Err(e @ AocCommandError::InputChanged(_)) => {
+            eprintln!("{ANSI_YELLOW}⚠️ {e}{ANSI_RESET}");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("failed to call aoc-cli: {e}");
+            process::exit(1);
+        }
+    }
 }
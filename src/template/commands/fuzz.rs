@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+use std::process::{self, Command};
+
+use crate::template::fuzz::{crash_path, has_crashed, Options, FINDINGS_EXIT_CODE};
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+pub fn handle(day: Option<Day>, cases: Option<usize>, timeout: Option<u64>, seed: Option<u64>) {
+    let days_to_run: HashSet<Day> =
+        day.map_or_else(|| all_days().collect(), |day| HashSet::from([day]));
+    let default = Options::default();
+
+    let cases = cases.unwrap_or(default.cases).to_string();
+    let timeout = timeout
+        .unwrap_or_else(|| u64::try_from(default.timeout.as_millis()).unwrap())
+        .to_string();
+    let seed = seed.unwrap_or(default.seed).to_string();
+
+    let mut failing = vec![];
+    let mut need_space = false;
+
+    // NOTE: use non-duplicate, sorted day values.
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        if !std::path::Path::new(&get_path_for_bin(day)).exists() {
+            continue;
+        }
+
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        // NOTE: debug builds are used on purpose, so integer overflows panic instead of wrapping.
+        let day_str = day.to_string();
+        let status = Command::new("cargo")
+            .args(["run", "--quiet", "--bin", &day_str, "--", "--fuzz"])
+            .args(["--cases", &cases, "--timeout", &timeout, "--seed", &seed])
+            .status();
+
+        match status.map(|s| s.code()) {
+            Ok(Some(0)) => {}
+            Ok(Some(FINDINGS_EXIT_CODE)) => failing.push(day),
+            _ if has_crashed(day) => {
+                println!(
+                    "{ANSI_RED}✖{ANSI_RESET} the process aborted (e.g. on a stack overflow), \
+                    input saved to \"{}\".",
+                    crash_path(day)
+                );
+                failing.push(day);
+            }
+            _ => {
+                eprintln!("Failed to run the solution of day {day}.");
+                failing.push(day);
+            }
+        }
+    }
+
+    println!();
+    if failing.is_empty() {
+        println!("{ANSI_GREEN}No failures found.{ANSI_RESET}");
+    } else {
+        let days: Vec<String> = failing.iter().map(ToString::to_string).collect();
+        println!(
+            "{ANSI_RED}Failures found in day(s) {}.{ANSI_RESET}",
+            days.join(", ")
+        );
+        process::exit(1);
+    }
+}
@@ -0,0 +1,20 @@
+use std::{fs, process};
+
+use crate::template::history::{recorded_days, render_report, render_svg, History};
+use crate::template::Day;
+
+pub fn handle(day: Option<Day>, svg_path: Option<String>) {
+    let history = History::read_from_file();
+
+    let days = day.map_or_else(|| recorded_days(&history), |day| vec![day]);
+
+    print!("{}", render_report(&history, &days));
+
+    if let Some(path) = svg_path {
+        if let Err(e) = fs::write(&path, render_svg(&history, &days)) {
+            eprintln!("Failed to write chart: {e}");
+            process::exit(1);
+        }
+        println!("Wrote chart to \"{path}\".");
+    }
+}
@@ -1,6 +1,13 @@
 pub mod all;
 pub mod download;
+pub mod fuzz;
+pub mod history;
 pub mod read;
 pub mod scaffold;
+pub mod scale;
 pub mod solve;
+pub mod status;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
+pub mod verify;
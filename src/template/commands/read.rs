@@ -1,10 +1,51 @@
-use std::process;
+use std::{
+    env, fs,
+    io::{self, IsTerminal, Write},
+    process::{self, Command, Stdio},
+};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{
+    aoc_cli::{self, DownloadMode},
+    markdown,
+    submissions::Submissions,
+    Day, ANSI_DIM, ANSI_RESET, ANSI_YELLOW,
+};
 
-pub fn handle(day: Day) {
+/// Widest column to wrap paragraphs at, even on wide terminals.
+const MAX_WIDTH: usize = 100;
+
+/// Renders the downloaded description of a day, and falls back to fetching it with aoc-cli.
+pub fn handle(day: Day, use_pager: bool) {
+    let path = aoc_cli::get_puzzle_path(day);
+
+    let Ok(mut description) = fs::read_to_string(&path) else {
+        fetch(day);
+        return;
+    };
+
+    // part two is only part of the description once part one is solved.
+    let part_one_solved = Submissions::read_from_file().correct(day, 1).is_some();
+    if part_one_solved && !markdown::has_part_two(&description) {
+        match refresh(day) {
+            Some(refreshed) => description = refreshed,
+            None => eprintln!(
+                "{ANSI_YELLOW}Part one is solved but part two could not be downloaded, showing \"{path}\".{ANSI_RESET}"
+            ),
+        }
+    }
+
+    let rendered = markdown::render(&description, terminal_size().0.min(MAX_WIDTH));
+    let footer = format!("\n{ANSI_DIM}Rendered from \"{path}\".{ANSI_RESET}\n");
+
+    if !use_pager || !page(&rendered) {
+        print!("{rendered}");
+    }
+    print!("{footer}");
+}
+
+fn fetch(day: Day) {
     if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        eprintln!("no downloaded description, and command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
@@ -13,3 +54,48 @@ pub fn handle(day: Day) {
         process::exit(1);
     };
 }
+
+/// Downloads the description again, to pick up part two.
+fn refresh(day: Day) -> Option<String> {
+    aoc_cli::check().ok()?;
+    aoc_cli::download(day, DownloadMode::Puzzle).ok()?;
+    fs::read_to_string(aoc_cli::get_puzzle_path(day)).ok()
+}
+
+/// Columns and rows of the terminal, from the environment variables set by most shells.
+fn terminal_size() -> (usize, usize) {
+    let size = |key: &str, default: usize| {
+        env::var(key)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default)
+    };
+    (size("COLUMNS", 80), size("LINES", 24))
+}
+
+/// Shows text that doesn't fit the terminal in `$PAGER`, or `less`. Returns whether it was shown.
+fn page(text: &str) -> bool {
+    if !io::stdout().is_terminal() || text.lines().count() < terminal_size().1 {
+        return false;
+    }
+
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less -R".into());
+    let mut words = pager.split_whitespace();
+    let Some(program) = words.next() else {
+        return false;
+    };
+
+    let Ok(mut child) = Command::new(program)
+        .args(words)
+        .stdin(Stdio::piped())
+        .spawn()
+    else {
+        return false;
+    };
+
+    // the pager may be quit before reading everything, which closes the pipe.
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait().is_ok()
+}
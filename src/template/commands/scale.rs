@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::scale::{describe_exponent, parse_summary, Options};
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_DIM, ANSI_RED, ANSI_RESET};
+
+/// The fitted exponent of a part, and its slowest measured run.
+type Complexity = Option<(f64, String)>;
+
+pub fn handle(day: Option<Day>, max_factor: Option<usize>, budget: Option<u64>) {
+    let days_to_run: HashSet<Day> =
+        day.map_or_else(|| all_days().collect(), |day| HashSet::from([day]));
+    let default = Options::default();
+
+    let max_factor = max_factor.unwrap_or(default.max_factor).to_string();
+    let budget = budget
+        .unwrap_or_else(|| u64::try_from(default.budget.as_millis()).unwrap())
+        .to_string();
+
+    let mut rows: Vec<(Day, [Complexity; 2])> = vec![];
+
+    // NOTE: use non-duplicate, sorted day values.
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        if !std::path::Path::new(&get_path_for_bin(day)).exists() {
+            continue;
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let day_str = day.to_string();
+        let child = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--release",
+                "--bin",
+                &day_str,
+                "--",
+                "--scale",
+            ])
+            .args(["--max-factor", &max_factor, "--budget", &budget])
+            .stdout(Stdio::piped())
+            .spawn();
+
+        let Ok(mut child) = child else {
+            eprintln!("Failed to run the solution of day {day}.");
+            continue;
+        };
+
+        let mut parts: [Complexity; 2] = [None, None];
+        if let Some(stdout) = child.stdout.take() {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                println!("{line}");
+                if let Some((part @ 1..=2, exponent, largest)) = parse_summary(&line) {
+                    parts[usize::from(part) - 1] = Some((exponent, largest));
+                }
+            }
+        }
+        let _ = child.wait();
+        println!();
+
+        if parts.iter().any(Option::is_some) {
+            rows.push((day, parts));
+        }
+    }
+
+    println!("{}", render_table(&rows));
+}
+
+fn render_table(rows: &[(Day, [Complexity; 2])]) -> String {
+    let mut table = String::new();
+    table.push_str(&format!("{ANSI_BOLD}Complexity{ANSI_RESET}\n"));
+
+    if rows.is_empty() {
+        table.push_str("No day could be scaled, register scalers with `solution!(…, scale = …)`.");
+        return table;
+    }
+
+    table.push_str(&format!(
+        "{ANSI_DIM}Day  {:<44}{}{ANSI_RESET}\n",
+        "Part 1", "Part 2"
+    ));
+
+    for (day, parts) in rows {
+        let cells = parts.each_ref().map(|part| match part {
+            Some((exponent, largest)) => {
+                let cell = format!(
+                    "n^{exponent:.2} {:<14} ({largest})",
+                    describe_exponent(*exponent)
+                );
+                // quadratic growth and worse is where optimization pays off.
+                if *exponent >= 1.8 {
+                    format!("{ANSI_RED}{cell:<44}{ANSI_RESET}")
+                } else {
+                    format!("{cell:<44}")
+                }
+            }
+            None => format!("{:<44}", "-"),
+        });
+
+        table.push_str(&format!("{day}   {}{}\n", cells[0], cells[1].trim_end()));
+    }
+
+    table
+}
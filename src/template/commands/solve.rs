@@ -2,7 +2,16 @@ use std::process::{Command, Stdio};
 
 use crate::template::Day;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+/// `force` skips the checks run before submitting, `retry` resubmits answers sent too soon after the previous one.
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    alloc_stats: bool,
+    submit_part: Option<u8>,
+    force: bool,
+    retry: bool,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -12,8 +21,16 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if release {
+            cmd_args.push("--release".to_string());
+        }
+
+        // the counting allocator would clash with the dhat allocator, so it is only enabled here.
+        if alloc_stats {
+            cmd_args.push("--features".to_string());
+            cmd_args.push("alloc-stats".to_string());
+        }
     }
 
     cmd_args.push("--".to_string());
@@ -21,6 +38,13 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
+
+        if force {
+            cmd_args.push("--force".to_string());
+        }
+        if retry {
+            cmd_args.push("--retry".to_string());
+        }
     }
 
     let mut cmd = Command::new("cargo")
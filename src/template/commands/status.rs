@@ -0,0 +1,19 @@
+use crate::template::all_days;
+use crate::template::status::{render_json, render_table, DayStatus};
+use crate::template::submissions::Submissions;
+use crate::template::timings::Timings;
+
+pub fn handle(json: bool, run_tests: bool) {
+    let submissions = Submissions::read_from_file();
+    let timings = Timings::read_from_file();
+
+    let statuses: Vec<DayStatus> = all_days()
+        .map(|day| DayStatus::collect(day, &submissions, &timings, run_tests))
+        .collect();
+
+    if json {
+        println!("{}", render_json(&statuses));
+    } else {
+        println!("{}", render_table(&statuses));
+    }
+}
@@ -1,10 +1,26 @@
 use std::collections::HashSet;
+use std::process;
 
-use crate::template::run_multi::run_multi;
+use crate::template::history::History;
+use crate::template::run_multi::{run_multi, CacheMode};
+use crate::template::submissions::Submissions;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, Day, ANSI_RESET, ANSI_YELLOW};
+
+pub fn handle(day: Option<Day>, run_all: bool, store: bool, alloc_stats: bool, jobs: usize) {
+    // parallel runs compete for the CPU, which distorts benchmarks.
+    if jobs > 1 {
+        if store {
+            eprintln!(
+                "Benchmarks of parallel runs can not be stored, run `time` without `--jobs`."
+            );
+            process::exit(1);
+        }
+        eprintln!(
+            "{ANSI_YELLOW}Running {jobs} days in parallel, timings are distorted.{ANSI_RESET}"
+        );
+    }
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,14 +37,21 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // benchmarks are always fresh, cached timings could come from a different machine load.
+    let timings = run_multi(&days_to_run, true, true, alloc_stats, jobs, CacheMode::Off)
+        .unwrap()
+        .timings;
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
+        if let Err(e) = History::append(&timings) {
+            eprintln!("Failed to append benchmarks to history: {e}");
+        }
+
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(merged_timings, &Submissions::read_from_file()) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
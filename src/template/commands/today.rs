@@ -0,0 +1,63 @@
+use std::{path::Path, process};
+
+use crate::template::{
+    aoc_cli::{self, DownloadMode},
+    commands::{read, scaffold},
+    unlock::{current_year, next_unlock, retry_with_backoff, wait_until, Clock, SystemClock},
+    Day,
+};
+
+/// Download attempts after waiting for an unlock, the server may take a moment to serve the input.
+const DOWNLOAD_ATTEMPTS: usize = 10;
+
+/// Scaffolds, downloads and renders the puzzle of the day.
+///
+/// With `wait`, counts down to the next unlock first, unless the puzzle of the day is unlocked and not scaffolded yet.
+pub fn handle(wait: bool) {
+    handle_with_clock(&SystemClock, wait);
+}
+
+fn handle_with_clock(clock: &impl Clock, wait: bool) {
+    let now = clock.now();
+    let is_scaffolded = |day: Day| Path::new(&format!("src/bin/{day}.rs")).exists();
+
+    let day = match Day::on(now).filter(|day| !wait || !is_scaffolded(*day)) {
+        Some(day) => day,
+        None if wait => {
+            let year = aoc_cli::get_year().map_or_else(|| current_year(now), i32::from);
+            let Some((day, unlock)) = next_unlock(now, year) else {
+                eprintln!("All puzzles of {year} are unlocked. Please use `scaffold` with a specific day.");
+                process::exit(1);
+            };
+            wait_until(clock, day, unlock);
+            day
+        }
+        None => {
+            eprintln!(
+                "`today` command can only be run between the 1st and \
+                the 25th of december. Please use `scaffold` with a specific day, \
+                or append `--wait` to wait for the next puzzle."
+            );
+            process::exit(1);
+        }
+    };
+
+    if !is_scaffolded(day) {
+        scaffold::handle(day, false);
+    }
+
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    let attempts = if wait { DOWNLOAD_ATTEMPTS } else { 1 };
+    if let Err(e) = retry_with_backoff(clock, attempts, || {
+        aoc_cli::download(day, DownloadMode::Both)
+    }) {
+        eprintln!("failed to download day {day}: {e}");
+        process::exit(1);
+    }
+
+    read::handle(day, true);
+}
@@ -0,0 +1,66 @@
+use std::process;
+
+use crate::template::all_days;
+use crate::template::run_multi::{run_multi, CacheMode};
+use crate::template::submissions::Submissions;
+use crate::template::{ANSI_BOLD, ANSI_DIM, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+/// Runs all solutions and compares their answers to the answers accepted on submission.
+pub fn handle(is_release: bool, jobs: usize, force: bool) {
+    let cache_mode = if force {
+        CacheMode::Refresh
+    } else {
+        CacheMode::Use
+    };
+
+    let Some(runs) = run_multi(
+        &all_days().collect(),
+        is_release,
+        false,
+        false,
+        jobs,
+        cache_mode,
+    ) else {
+        process::exit(1);
+    };
+
+    let submissions = Submissions::read_from_file();
+    let mut failures = 0;
+
+    println!("\n{ANSI_BOLD}Verify{ANSI_RESET}");
+
+    for (day, answers) in &runs.answers {
+        let cells: Vec<String> = (1..=2u8)
+            .zip(answers)
+            .map(|(part, answer)| {
+                let expected = submissions.correct(*day, part).map(|s| &s.answer);
+                match (answer, expected) {
+                    (Some(answer), Some(expected)) if answer == expected => {
+                        format!("Part {part}: {ANSI_GREEN}✔{ANSI_RESET}")
+                    }
+                    (answer, Some(expected)) => {
+                        failures += 1;
+                        format!(
+                            "Part {part}: {ANSI_RED}✖ {} (expected {expected}){ANSI_RESET}",
+                            answer.as_deref().unwrap_or("no answer")
+                        )
+                    }
+                    (_, None) => format!("Part {part}: {ANSI_DIM}no accepted answer{ANSI_RESET}"),
+                }
+            })
+            .collect();
+
+        let cached = if runs.cached.contains(day) {
+            format!("  {ANSI_DIM}(cached){ANSI_RESET}")
+        } else {
+            String::new()
+        };
+
+        println!("Day {day}  {}{cached}", cells.join("  "));
+    }
+
+    if failures > 0 {
+        eprintln!("{ANSI_RED}{failures} answer(s) differ from the accepted ones.{ANSI_RESET}");
+        process::exit(1);
+    }
+}
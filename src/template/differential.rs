@@ -0,0 +1,297 @@
+/// A harness for differential testing: random but valid puzzle inputs are generated, and several
+/// implementations of a part (e.g. variants or a brute-force oracle) must agree on all of them.
+/// Failing inputs are shrunk to a minimal reproduction, which is saved to `data/examples`.
+use std::{
+    env,
+    fmt::{Debug, Display},
+    fs,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::template::{rng::Rng, Day};
+
+/// An implementation of a part, along with its name.
+pub type Implementation<T> = (&'static str, fn(&str) -> Option<T>);
+
+/// Generates random but valid puzzle inputs for a day.
+pub trait Generator {
+    type Value: Clone + Debug;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+
+    /// Renders a value to the textual puzzle input.
+    fn render(&self, value: &Self::Value) -> String;
+
+    /// Returns smaller candidates of a value, which are tried in order while shrinking.
+    fn shrink(&self, _value: &Self::Value) -> Vec<Self::Value> {
+        vec![]
+    }
+}
+
+/// A generator for inputs made of a list of items, e.g. one item per line or per block.
+/// Shrinks by removing items, then by shrinking single items.
+pub struct ListOf<T> {
+    pub len: RangeInclusive<usize>,
+    pub separator: &'static str,
+    pub item: fn(&mut Rng) -> T,
+    pub render: fn(&T) -> String,
+    pub shrink: fn(&T) -> Vec<T>,
+}
+
+/// An item shrinker for items that can't be made any smaller.
+pub fn no_shrink<T>(_: &T) -> Vec<T> {
+    vec![]
+}
+
+impl<T: Clone + Debug> Generator for ListOf<T> {
+    type Value = Vec<T>;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        let (min, max) = (*self.len.start() as u64, *self.len.end() as u64);
+        let len = usize::try_from(rng.range(min..=max)).unwrap();
+        (0..len).map(|_| (self.item)(rng)).collect()
+    }
+
+    fn render(&self, value: &Self::Value) -> String {
+        let items: Vec<String> = value.iter().map(self.render).collect();
+        let mut s = items.join(self.separator);
+        s.push('\n');
+        s
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let min = *self.len.start();
+        let mut candidates = vec![];
+
+        // remove halves first, then single items.
+        if value.len() / 2 >= min && value.len() > 1 {
+            let half = value.len() / 2;
+            candidates.push(value[..half].to_vec());
+            candidates.push(value[half..].to_vec());
+        }
+
+        if value.len() > min {
+            for i in 0..value.len() {
+                let mut smaller = value.clone();
+                smaller.remove(i);
+                candidates.push(smaller);
+            }
+        }
+
+        for (i, item) in value.iter().enumerate() {
+            for smaller_item in (self.shrink)(item) {
+                let mut smaller = value.clone();
+                smaller[i] = smaller_item;
+                candidates.push(smaller);
+            }
+        }
+
+        candidates
+    }
+}
+
+/// How an input made implementations disagree.
+enum Failure<T> {
+    Mismatch(Vec<(&'static str, Option<T>)>),
+    Panic(&'static str, String),
+}
+
+impl<T: Debug> Display for Failure<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Mismatch(results) => {
+                for (name, result) in results {
+                    writeln!(f, "  {name}: {result:?}")?;
+                }
+                Ok(())
+            }
+            // NOTE: only keep the first line, errors may carry a backtrace.
+            Failure::Panic(name, message) => writeln!(
+                f,
+                "  {name} panicked: {}",
+                message.lines().next().unwrap_or_default()
+            ),
+        }
+    }
+}
+
+fn run_all<T: PartialEq + Debug>(
+    implementations: &[Implementation<T>],
+    input: &str,
+) -> Option<Failure<T>> {
+    let mut results = vec![];
+
+    for (name, func) in implementations {
+        match panic::catch_unwind(AssertUnwindSafe(|| func(input))) {
+            Ok(result) => results.push((*name, result)),
+            Err(e) => {
+                let message = e
+                    .downcast_ref::<&str>()
+                    .map(ToString::to_string)
+                    .or_else(|| e.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".into());
+                return Some(Failure::Panic(name, message));
+            }
+        }
+    }
+
+    if results.windows(2).all(|w| w[0].1 == w[1].1) {
+        None
+    } else {
+        Some(Failure::Mismatch(results))
+    }
+}
+
+fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Path of the reproduction of a failure, e.g. `data/examples/13-repro-1.txt`.
+pub fn repro_path(day: Day, part: u8) -> String {
+    format!("data/examples/{day}-repro-{part}.txt")
+}
+
+/// Checks that all implementations agree on random inputs. The first implementation is the reference.
+///
+/// The number of cases and the seed can be set with the `AOC_CASES` and `AOC_SEED` environment variables.
+///
+/// # Panics
+/// Panics with the shrunk input if implementations disagree or one of them panics.
+/// The shrunk input is saved to [`repro_path`].
+pub fn check<G: Generator, T: PartialEq + Debug>(
+    day: Day,
+    part: u8,
+    generator: &G,
+    implementations: &[Implementation<T>],
+) {
+    let cases: usize = env_or("AOC_CASES", 100);
+    let seed: u64 = env_or(
+        "AOC_SEED",
+        u64::from(day.into_inner()) << 8 | u64::from(part),
+    );
+
+    let mut rng = Rng::new(seed);
+
+    for case in 0..cases {
+        let value = generator.generate(&mut rng);
+        if run_all(implementations, &generator.render(&value)).is_none() {
+            continue;
+        }
+
+        let value = shrink(generator, implementations, value);
+        let input = generator.render(&value);
+        let failure = run_all(implementations, &input).unwrap();
+
+        let path = repro_path(day, part);
+        let saved = match fs::write(&path, &input) {
+            Ok(()) => format!("saved to \"{path}\""),
+            Err(e) => format!("could not save to \"{path}\": {e}"),
+        };
+
+        panic!(
+            "implementations disagree on case {case} (seed {seed}), {saved}.\n\
+            {failure}\
+            --- input ---\n{input}"
+        );
+    }
+}
+
+/// Greedily replaces the value by its first smaller candidate that still fails, until none does.
+fn shrink<G: Generator, T: PartialEq + Debug>(
+    generator: &G,
+    implementations: &[Implementation<T>],
+    mut value: G::Value,
+) -> G::Value {
+    const MAX_STEPS: usize = 1000;
+
+    // silence panics of the implementations while shrinking.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    for _ in 0..MAX_STEPS {
+        let smaller = generator
+            .shrink(&value)
+            .into_iter()
+            .find(|candidate| run_all(implementations, &generator.render(candidate)).is_some());
+
+        match smaller {
+            Some(smaller) => value = smaller,
+            None => break,
+        }
+    }
+
+    panic::set_hook(hook);
+    value
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{no_shrink, run_all, shrink, ListOf};
+    use crate::template::rng::Rng;
+
+    fn sum(input: &str) -> Option<u64> {
+        Some(input.lines().filter_map(|l| l.parse::<u64>().ok()).sum())
+    }
+
+    // wrong as soon as a number above 50 is present.
+    fn capped_sum(input: &str) -> Option<u64> {
+        Some(
+            input
+                .lines()
+                .filter_map(|l| l.parse::<u64>().ok())
+                .map(|n| n.min(50))
+                .sum(),
+        )
+    }
+
+    fn numbers() -> ListOf<u64> {
+        ListOf {
+            len: 1..=20,
+            separator: "\n",
+            item: |rng: &mut Rng| rng.range(0..=100),
+            render: u64::to_string,
+            shrink: |n: &u64| if *n > 0 { vec![n / 2, n - 1] } else { vec![] },
+        }
+    }
+
+    #[test]
+    fn agreeing_implementations_pass() {
+        let implementations = [("a", sum as fn(&str) -> _), ("b", sum)];
+        assert!(run_all(&implementations, "1\n2\n").is_none());
+    }
+
+    #[test]
+    fn shrinks_to_minimal_input() {
+        let implementations = [("sum", sum as fn(&str) -> _), ("capped", capped_sum)];
+        let value = shrink(&numbers(), &implementations, vec![3, 99, 7, 64, 0]);
+        assert_eq!(value, vec![51]);
+    }
+
+    #[test]
+    fn reports_panics() {
+        let implementations = [
+            ("sum", sum as fn(&str) -> _),
+            ("panics", |_: &str| -> Option<u64> { panic!("boom") }),
+        ];
+        let failure = run_all(&implementations, "1\n");
+        assert!(failure
+            .unwrap()
+            .to_string()
+            .contains("panics panicked: boom"));
+    }
+
+    #[test]
+    fn does_not_shrink_items_with_no_shrink() {
+        let generator = ListOf {
+            shrink: no_shrink,
+            ..numbers()
+        };
+        let implementations = [("sum", sum as fn(&str) -> _), ("capped", capped_sum)];
+        let value = shrink(&generator, &implementations, vec![3, 99]);
+        assert_eq!(value, vec![99]);
+    }
+}
@@ -0,0 +1,540 @@
+/// Robustness testing: example and real inputs are mutated and fed to the solution parts, and
+/// panics, overflows and hangs are reported along with a minimized input that triggers them.
+///
+/// Runs inside a day binary when it is invoked with `--fuzz`, which `cargo fuzz` does for every day.
+use std::{
+    fmt::Display,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    process,
+    sync::{mpsc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use crate::template::{rng::Rng, runner::Part, Day, ANSI_BOLD, ANSI_DIM, ANSI_RED, ANSI_RESET};
+
+/// Exit code of a day binary that found failures.
+pub const FINDINGS_EXIT_CODE: i32 = 2;
+
+const FUZZ_DIR: &str = "data/fuzz";
+
+/// Stack size of the threads that run the parts. Larger than the default, so deep recursion on
+/// valid inputs does not abort the process.
+const STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Maximum number of runs spent minimizing a single input.
+const MINIMIZE_BUDGET: usize = 500;
+
+/// Inputs that are printed with more lines are cut off, the full input is saved to a file.
+const PREVIEW_LINES: usize = 10;
+
+/// Location of the last panic, recorded by the panic hook installed while fuzzing.
+static LAST_PANIC_LOCATION: Mutex<Option<String>> = Mutex::new(None);
+
+pub fn is_requested() -> bool {
+    std::env::args().any(|x| x == "--fuzz")
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mutation {
+    Truncation,
+    StrayCharacter,
+    MissingLine,
+    HugeNumber,
+}
+
+impl Mutation {
+    pub const ALL: [Mutation; 4] = [
+        Mutation::Truncation,
+        Mutation::StrayCharacter,
+        Mutation::MissingLine,
+        Mutation::HugeNumber,
+    ];
+
+    pub fn apply(self, input: &str, rng: &mut Rng) -> String {
+        match self {
+            Mutation::Truncation => {
+                let at = random_boundary(input, rng);
+                input[..at].to_string()
+            }
+            Mutation::StrayCharacter => {
+                const STRAY: [char; 10] = ['#', '.', '-', '+', 'x', ' ', ',', ':', '\n', 'é'];
+                let at = random_boundary(input, rng);
+                let mut s = input.to_string();
+                s.insert(at, *rng.choose(&STRAY));
+                s
+            }
+            Mutation::MissingLine => {
+                let mut lines: Vec<&str> = input.lines().collect();
+                if !lines.is_empty() {
+                    lines.remove(rng.index(lines.len()));
+                }
+                join_lines(&lines, input)
+            }
+            Mutation::HugeNumber => {
+                const HUGE: [&str; 4] = [
+                    "18446744073709551615",
+                    "9223372036854775807",
+                    "-9223372036854775808",
+                    "99999999999999999999999",
+                ];
+                let huge = *rng.choose(&HUGE);
+                let numbers = number_spans(input);
+                if numbers.is_empty() {
+                    format!("{input}{huge}")
+                } else {
+                    let (start, end) = numbers[rng.index(numbers.len())];
+                    format!("{}{huge}{}", &input[..start], &input[end..])
+                }
+            }
+        }
+    }
+}
+
+impl Display for Mutation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Mutation::Truncation => "truncation",
+            Mutation::StrayCharacter => "stray character",
+            Mutation::MissingLine => "missing line",
+            Mutation::HugeNumber => "huge number",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Returns a random char boundary of the input, its end included.
+fn random_boundary(input: &str, rng: &mut Rng) -> usize {
+    let boundaries: Vec<usize> = input
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain([input.len()])
+        .collect();
+    *rng.choose(&boundaries)
+}
+
+/// Returns the byte ranges of the unsigned numbers in the input.
+fn number_spans(input: &str) -> Vec<(usize, usize)> {
+    let mut spans = vec![];
+    let mut start = None;
+    for (i, c) in input.char_indices().chain([(input.len(), ' ')]) {
+        match (c.is_ascii_digit(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+/// Joins lines, keeping the trailing newline of the original input.
+fn join_lines(lines: &[&str], original: &str) -> String {
+    let mut s = lines.join("\n");
+    if original.ends_with('\n') && !s.is_empty() {
+        s.push('\n');
+    }
+    s
+}
+
+/// How a part failed on an input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    Panic { location: String, message: String },
+    Overflow { location: String, message: String },
+    Hang,
+}
+
+impl Failure {
+    fn from_panic(message: String) -> Self {
+        let location = LAST_PANIC_LOCATION
+            .lock()
+            .unwrap()
+            .take()
+            .unwrap_or_else(|| "unknown location".into());
+        // NOTE: only keep the first line, errors may carry a backtrace.
+        let message = message.lines().next().unwrap_or_default().to_string();
+
+        if message.contains("overflow") {
+            Failure::Overflow { location, message }
+        } else {
+            Failure::Panic { location, message }
+        }
+    }
+
+    /// Two failures are the same if they are of the same kind and happen at the same location.
+    fn is_same(&self, other: &Failure) -> bool {
+        match (self, other) {
+            (Failure::Panic { location: a, .. }, Failure::Panic { location: b, .. })
+            | (Failure::Overflow { location: a, .. }, Failure::Overflow { location: b, .. }) => {
+                a == b
+            }
+            (Failure::Hang, Failure::Hang) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Panic { location, message } => write!(f, "panic at {location}: {message}"),
+            Failure::Overflow { location, message } => {
+                write!(f, "overflow at {location}: {message}")
+            }
+            Failure::Hang => write!(f, "hang"),
+        }
+    }
+}
+
+/// Runs a part on an input in a separate thread, and gives up waiting after the timeout.
+///
+/// NOTE: a thread that hangs cannot be stopped and keeps running until the process exits.
+fn run_with_timeout(
+    func: fn(&str) -> Option<String>,
+    input: &str,
+    timeout: Duration,
+) -> Option<Failure> {
+    let input = input.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| func(&input)));
+            let message = result.err().map(|e| {
+                e.downcast_ref::<&str>()
+                    .map(ToString::to_string)
+                    .or_else(|| e.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".into())
+            });
+            // the receiver is gone if the run timed out.
+            let _ = tx.send(message);
+        })
+        .expect("could not spawn fuzzing thread");
+
+    match rx.recv_timeout(timeout) {
+        Ok(None) => None,
+        Ok(Some(message)) => Some(Failure::from_panic(message)),
+        Err(_) => Some(Failure::Hang),
+    }
+}
+
+/// Removes chunks of units (lines, then characters) as long as the input still fails the same way.
+/// Chunks are halved whenever no chunk of the current size can be removed.
+fn reduce(
+    units: Vec<&str>,
+    separator: &str,
+    budget: &mut usize,
+    fails: &mut impl FnMut(&str) -> bool,
+) -> Vec<String> {
+    let mut units: Vec<String> = units.into_iter().map(ToString::to_string).collect();
+    let mut chunk = units.len().div_ceil(2);
+
+    while chunk > 0 && *budget > 0 {
+        let mut i = 0;
+        while i < units.len() && *budget > 0 {
+            let end = (i + chunk).min(units.len());
+            let candidate: Vec<String> = [&units[..i], &units[end..]].concat();
+
+            *budget -= 1;
+            if fails(&candidate.join(separator)) {
+                units = candidate;
+            } else {
+                i += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+
+    units
+}
+
+/// Shrinks an input while it keeps failing the same way. Inputs that hang are not minimized,
+/// since every attempt would leave a thread running.
+fn minimize(
+    func: fn(&str) -> Option<String>,
+    input: &str,
+    failure: &Failure,
+    timeout: Duration,
+) -> String {
+    if *failure == Failure::Hang {
+        return input.to_string();
+    }
+
+    let mut fails = |candidate: &str| {
+        run_with_timeout(func, candidate, timeout).is_some_and(|f| f.is_same(failure))
+    };
+    let mut budget = MINIMIZE_BUDGET;
+
+    let lines = reduce(input.lines().collect(), "\n", &mut budget, &mut fails);
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    let input = join_lines(&lines, input);
+
+    let chars: Vec<&str> = input
+        .char_indices()
+        .map(|(i, c)| &input[i..i + c.len_utf8()])
+        .collect();
+    reduce(chars, "", &mut budget, &mut fails).concat()
+}
+
+pub struct Options {
+    pub cases: usize,
+    pub timeout: Duration,
+    pub seed: u64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            cases: 50,
+            timeout: Duration::from_secs(5),
+            seed: 0,
+        }
+    }
+}
+
+impl Options {
+    /// Reads the options passed by `cargo fuzz` to the day binary.
+    fn from_args() -> Self {
+        let mut args = pico_args::Arguments::from_env();
+        let default = Options::default();
+
+        let cases = args.opt_value_from_str("--cases").ok().flatten();
+        let timeout = args.opt_value_from_str("--timeout").ok().flatten();
+        let seed = args.opt_value_from_str("--seed").ok().flatten();
+
+        Options {
+            cases: cases.unwrap_or(default.cases),
+            timeout: timeout.map_or(default.timeout, Duration::from_millis),
+            seed: seed.unwrap_or(default.seed),
+        }
+    }
+}
+
+/// Path of the input being run, kept in case it aborts the process (e.g. with a stack overflow).
+pub fn crash_path(day: Day) -> String {
+    format!("{FUZZ_DIR}/{day}-crash.txt")
+}
+
+fn finding_path(day: Day, index: usize) -> String {
+    format!("{FUZZ_DIR}/{day}-{index}.txt")
+}
+
+/// Returns the examples and the real input of a day, which are mutated.
+fn seed_inputs(day: Day) -> Vec<(String, String)> {
+    let mut paths: Vec<String> = fs::read_dir("data/examples")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| {
+            name.strip_prefix(&day.to_string()).is_some_and(|rest| {
+                rest == ".txt" || rest.starts_with('-') && rest.ends_with(".txt")
+            })
+        })
+        .map(|name| format!("data/examples/{name}"))
+        .collect();
+    paths.sort();
+    paths.push(format!("data/inputs/{day}.txt"));
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let content = fs::read_to_string(&path).ok()?;
+            Some((path, content))
+        })
+        .collect()
+}
+
+struct Finding {
+    part: u8,
+    failure: Failure,
+    source: String,
+    mutations: Vec<Mutation>,
+    input: String,
+}
+
+impl Finding {
+    fn report(&self, path: &str) {
+        let mutations: Vec<String> = self.mutations.iter().map(ToString::to_string).collect();
+
+        println!(
+            "{ANSI_RED}✖{ANSI_RESET} {ANSI_BOLD}Part {}{ANSI_RESET}: {}",
+            self.part, self.failure
+        );
+        println!(
+            "  {ANSI_DIM}from {} ({}), minimized input saved to \"{path}\":{ANSI_RESET}",
+            self.source,
+            mutations.join(", ")
+        );
+
+        let lines: Vec<&str> = self.input.lines().collect();
+        if lines.is_empty() {
+            println!("  │ {ANSI_DIM}(empty input){ANSI_RESET}");
+        }
+        for line in lines.iter().take(PREVIEW_LINES) {
+            println!("  │ {line}");
+        }
+        if lines.len() > PREVIEW_LINES {
+            println!("  │ … {} more lines", lines.len() - PREVIEW_LINES);
+        }
+    }
+}
+
+/// Fuzzes every part of a day, then exits with [`FINDINGS_EXIT_CODE`] if anything failed.
+pub fn run(day: Day, parts: &[Part]) {
+    let options = Options::from_args();
+    let seeds = seed_inputs(day);
+
+    if seeds.is_empty() {
+        println!("No example or input to mutate.");
+        return;
+    }
+
+    if let Err(e) = fs::create_dir_all(FUZZ_DIR) {
+        eprintln!("Failed to create \"{FUZZ_DIR}\": {e}");
+        process::exit(1);
+    }
+
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        *LAST_PANIC_LOCATION.lock().unwrap() = info.location().map(ToString::to_string);
+    }));
+
+    let mut rng = Rng::new(options.seed);
+    let mut findings: Vec<Finding> = vec![];
+
+    for &(part, func) in parts {
+        for _ in 0..options.cases {
+            let (source, mut input) = rng.choose(&seeds).clone();
+            let mutations: Vec<Mutation> = (0..rng.range(1..=3))
+                .map(|_| *rng.choose(&Mutation::ALL))
+                .collect();
+            for mutation in &mutations {
+                input = mutation.apply(&input, &mut rng);
+            }
+
+            // failing to keep the input only matters if the process aborts.
+            let _ = fs::write(crash_path(day), &input);
+
+            let Some(failure) = run_with_timeout(func, &input, options.timeout) else {
+                continue;
+            };
+
+            if findings
+                .iter()
+                .any(|f| f.part == part && f.failure.is_same(&failure))
+            {
+                continue;
+            }
+
+            let finding = Finding {
+                part,
+                input: minimize(func, &input, &failure, options.timeout),
+                failure,
+                source,
+                mutations,
+            };
+
+            let path = finding_path(day, findings.len() + 1);
+            if let Err(e) = fs::write(&path, &finding.input) {
+                eprintln!("Failed to save \"{path}\": {e}");
+            }
+            finding.report(&path);
+
+            let is_hang = finding.failure == Failure::Hang;
+            findings.push(finding);
+
+            if is_hang {
+                println!("  {ANSI_DIM}skipping the remaining cases of part {part}, it is still running.{ANSI_RESET}");
+                break;
+            }
+        }
+    }
+
+    panic::set_hook(hook);
+    let _ = fs::remove_file(crash_path(day));
+
+    if findings.is_empty() {
+        println!("No failures in {} mutated inputs per part.", options.cases);
+    } else {
+        println!("{} failure(s) found.", findings.len());
+        process::exit(FINDINGS_EXIT_CODE);
+    }
+}
+
+/// Returns whether a day binary left an input behind that aborted it.
+pub fn has_crashed(day: Day) -> bool {
+    Path::new(&crash_path(day)).exists()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{minimize, number_spans, run_with_timeout, Failure, Mutation};
+    use crate::template::rng::Rng;
+
+    fn parse_sum(input: &str) -> Option<String> {
+        let sum: u8 = input.lines().map(|l| l.parse::<u8>().unwrap()).sum();
+        Some(sum.to_string())
+    }
+
+    fn spin(_: &str) -> Option<String> {
+        loop {
+            std::hint::spin_loop();
+        }
+    }
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn finds_number_spans() {
+        assert_eq!(number_spans("a12 b3\n"), vec![(1, 3), (5, 6)]);
+        assert_eq!(number_spans("42"), vec![(0, 2)]);
+    }
+
+    #[test]
+    fn mutations_keep_char_boundaries() {
+        let mut rng = Rng::new(3);
+        for _ in 0..200 {
+            for mutation in Mutation::ALL {
+                mutation.apply("é1\nàb22\n", &mut rng);
+            }
+        }
+    }
+
+    #[test]
+    fn classifies_failures() {
+        assert_eq!(run_with_timeout(parse_sum, "1\n2\n", TIMEOUT), None);
+        assert!(matches!(
+            run_with_timeout(parse_sum, "1\nx\n", TIMEOUT),
+            Some(Failure::Panic { .. })
+        ));
+        assert!(matches!(
+            run_with_timeout(parse_sum, "200\n100\n", TIMEOUT),
+            Some(Failure::Overflow { .. })
+        ));
+        assert_eq!(
+            run_with_timeout(spin, "", Duration::from_millis(10)),
+            Some(Failure::Hang)
+        );
+    }
+
+    #[test]
+    fn minimizes_failing_inputs() {
+        fn reject_x(input: &str) -> Option<String> {
+            assert!(!input.contains('x'), "unexpected x");
+            Some(input.len().to_string())
+        }
+
+        let input = "1\n2\n3\n4x\n5\n";
+        let failure = run_with_timeout(reject_x, input, TIMEOUT).unwrap();
+        assert_eq!(minimize(reject_x, input, &failure, TIMEOUT), "x");
+    }
+}
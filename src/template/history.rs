@@ -0,0 +1,544 @@
+/// Keeps every stored benchmark run, so the evolution of a day's performance can be reported.
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    fs,
+    io::Error,
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    all_days,
+    timings::{Timing, Timings},
+    Day, ANSI_BOLD, ANSI_DIM, ANSI_GREEN, ANSI_RED, ANSI_RESET,
+};
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.json";
+
+/// The timings of a single `time --store` invocation.
+#[derive(Clone, Debug)]
+pub struct Run {
+    /// Output of `git describe --always --dirty`.
+    pub revision: String,
+    /// UTC date of the run, formatted as `YYYY-MM-DD`.
+    pub date: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub timings: Timings,
+}
+
+impl Run {
+    /// Creates a run for the current git revision and time.
+    pub fn new(timings: Timings) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        Run {
+            revision: current_revision(),
+            date: format_date(timestamp),
+            timestamp,
+            timings,
+        }
+    }
+}
+
+/// The timing of a day in a run, along with the index of that run.
+type Point<'a> = (usize, &'a Run, &'a Timing);
+
+/// All stored runs, oldest first.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+impl History {
+    /// Dehydrate history to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(HISTORY_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate history from a JSON file. If not present, returns an empty history.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(HISTORY_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(History::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Appends the timings of a run to the file on disk.
+    pub fn append(timings: &Timings) -> Result<(), Error> {
+        let mut history = Self::read_from_file();
+        history.runs.push(Run::new(timings.clone()));
+        history.store_file()
+    }
+
+    /// Every run that benched `day`, along with its run index.
+    pub fn series(&self, day: Day) -> Vec<Point<'_>> {
+        self.runs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, run)| {
+                let timing = run
+                    .timings
+                    .data
+                    .iter()
+                    .find(|t| t.day == day && t.total_nanos > 0.0)?;
+                Some((i, run, timing))
+            })
+            .collect()
+    }
+}
+
+/// Returns a description of the checked out revision, or `unknown` outside of a git repository.
+fn current_revision() -> String {
+    Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|revision| !revision.is_empty())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+/// Formats a unix timestamp as a `YYYY-MM-DD` date in UTC.
+fn format_date(timestamp: u64) -> String {
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = i64::try_from(timestamp / 86_400).unwrap_or_default() + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+/// Renders a series of values as a unicode sparkline.
+fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    values
+        .iter()
+        .map(|v| {
+            if max <= min {
+                return BARS[0];
+            }
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let index = (((v - min) / (max - min)) * 7.0).round() as usize;
+            BARS[index.min(7)]
+        })
+        .collect()
+}
+
+/// Formats the relative change from `from` to `to`. Speedups are green, slowdowns red.
+fn format_change(from: f64, to: f64) -> String {
+    let change = (to - from) / from * 100.0;
+    let color = if change < -1.0 {
+        ANSI_GREEN
+    } else if change > 1.0 {
+        ANSI_RED
+    } else {
+        ANSI_DIM
+    };
+    format!("{color}{change:+.1}%{ANSI_RESET}")
+}
+
+/// Renders per-day trends: latest timing, change since the previous and first run, and best/worst runs.
+pub fn render_report(history: &History, days: &[Day]) -> String {
+    let mut out = String::new();
+
+    for day in days {
+        let series = history.series(*day);
+        let Some((_, latest_run, latest)) = series.last() else {
+            continue;
+        };
+
+        let totals: Vec<f64> = series.iter().map(|(_, _, t)| t.total_nanos).collect();
+
+        let (_, best_run, best) = series
+            .iter()
+            .min_by(|a, b| a.2.total_nanos.total_cmp(&b.2.total_nanos))
+            .unwrap();
+        let (_, worst_run, worst) = series
+            .iter()
+            .max_by(|a, b| a.2.total_nanos.total_cmp(&b.2.total_nanos))
+            .unwrap();
+
+        let _ = writeln!(
+            out,
+            "{ANSI_BOLD}Day {day}{ANSI_RESET} {}",
+            sparkline(&totals)
+        );
+        let _ = writeln!(
+            out,
+            "  latest: {} ({}, {}) part 1: {}, part 2: {}",
+            format_nanos(latest.total_nanos),
+            latest_run.revision,
+            latest_run.date,
+            latest.part_1.as_deref().unwrap_or("-"),
+            latest.part_2.as_deref().unwrap_or("-"),
+        );
+
+        if series.len() > 1 {
+            let previous = totals[totals.len() - 2];
+            let _ = writeln!(
+                out,
+                "  change: {} since previous, {} since first of {} runs",
+                format_change(previous, latest.total_nanos),
+                format_change(totals[0], latest.total_nanos),
+                series.len(),
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "  best:   {} ({}, {})",
+            format_nanos(best.total_nanos),
+            best_run.revision,
+            best_run.date
+        );
+        let _ = writeln!(
+            out,
+            "  worst:  {} ({}, {})",
+            format_nanos(worst.total_nanos),
+            worst_run.revision,
+            worst_run.date
+        );
+        out.push('\n');
+    }
+
+    if out.is_empty() {
+        out.push_str("No benchmark history. Run `cargo time --store` to record one.\n");
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+const CHART_WIDTH: f64 = 960.0;
+const CHART_HEIGHT: f64 = 480.0;
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_RIGHT: f64 = 110.0;
+const MARGIN_TOP: f64 = 30.0;
+const MARGIN_BOTTOM: f64 = 90.0;
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Renders a self-contained SVG line chart of the total time of each day across runs.
+/// The y axis is logarithmic, since days range from microseconds to seconds.
+pub fn render_svg(history: &History, days: &[Day]) -> String {
+    let series: Vec<(Day, Vec<Point>)> = days
+        .iter()
+        .map(|day| (*day, history.series(*day)))
+        .filter(|(_, s)| !s.is_empty())
+        .collect();
+
+    let values = series
+        .iter()
+        .flat_map(|(_, s)| s.iter().map(|(_, _, t)| t.total_nanos.log10()));
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+        (min.min(v), max.max(v))
+    });
+    let (min, max) = if min.is_finite() {
+        (min.floor(), max.ceil().max(min.floor() + 1.0))
+    } else {
+        (0.0, 1.0)
+    };
+
+    let plot_width = CHART_WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plot_height = CHART_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+
+    #[allow(clippy::cast_precision_loss)]
+    let x = |run: usize| {
+        let runs = history.runs.len().max(2) - 1;
+        MARGIN_LEFT + plot_width * run as f64 / runs as f64
+    };
+    let y = |nanos: f64| MARGIN_TOP + plot_height * (max - nanos.log10()) / (max - min);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{CHART_WIDTH}" height="{CHART_HEIGHT}" font-family="sans-serif" font-size="11">"#
+    );
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+
+    // y axis: one grid line per power of ten.
+    #[allow(clippy::cast_possible_truncation)]
+    for exponent in (min as i32)..=(max as i32) {
+        let nanos = 10_f64.powi(exponent);
+        let y = y(nanos);
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{MARGIN_LEFT}" x2="{}" y1="{y:.1}" y2="{y:.1}" stroke="#ddd"/><text x="{}" y="{:.1}" text-anchor="end">{}</text>"##,
+            CHART_WIDTH - MARGIN_RIGHT,
+            MARGIN_LEFT - 6.0,
+            y + 4.0,
+            format_nanos(nanos),
+        );
+    }
+
+    // x axis: one label per run.
+    for (i, run) in history.runs.iter().enumerate() {
+        let x = x(i);
+        let label = escape_xml(&format!("{} {}", run.revision, run.date));
+        let _ = writeln!(
+            svg,
+            r##"<text transform="translate({x:.1},{:.1}) rotate(-45)" text-anchor="end" fill="#555">{label}</text>"##,
+            CHART_HEIGHT - MARGIN_BOTTOM + 12.0,
+        );
+    }
+
+    for (index, (day, points)) in series.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let hue = (index as f64 * 360.0 / series.len() as f64).round();
+        let color = format!("hsl({hue},70%,45%)");
+
+        let polyline: Vec<String> = points
+            .iter()
+            .map(|(i, _, t)| format!("{:.1},{:.1}", x(*i), y(t.total_nanos)))
+            .collect();
+        let _ = writeln!(
+            svg,
+            r#"<polyline fill="none" stroke="{color}" stroke-width="2" points="{}"/>"#,
+            polyline.join(" ")
+        );
+
+        for (i, run, timing) in points {
+            let title = escape_xml(&format!(
+                "Day {day}: {} ({} {})",
+                format_nanos(timing.total_nanos),
+                run.revision,
+                run.date
+            ));
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{:.1}" cy="{:.1}" r="3" fill="{color}"><title>{title}</title></circle>"#,
+                x(*i),
+                y(timing.total_nanos),
+            );
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let legend_y = MARGIN_TOP + index as f64 * 14.0;
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{legend_y:.1}" fill="{color}">Day {day}</text>"#,
+            CHART_WIDTH - MARGIN_RIGHT + 12.0,
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Days that appear in at least one run, in order.
+pub fn recorded_days(history: &History) -> Vec<Day> {
+    all_days()
+        .filter(|day| !history.series(*day).is_empty())
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<History> for JsonValue {
+    fn from(value: History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.runs.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(History {
+            runs: json_data
+                .iter()
+                .map(Run::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Run> for JsonValue {
+    fn from(value: &Run) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("revision".into(), JsonValue::String(value.revision.clone()));
+        map.insert("date".into(), JsonValue::String(value.date.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "timings".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Run {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        let revision = json
+            .get("revision")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected run.revision to be a string.")?;
+
+        let date = json
+            .get("date")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected run.date to be a string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|t| *t as u64)
+            .ok_or("Expected run.timestamp to be a number.")?;
+
+        let timings = json
+            .get("timings")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected run.timings to be an array.")?;
+
+        Ok(Run {
+            revision: revision.clone(),
+            date: date.clone(),
+            timestamp,
+            timings: Timings {
+                data: timings
+                    .iter()
+                    .map(Timing::try_from)
+                    .collect::<Result<_, _>>()?,
+            },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_date, render_report, render_svg, sparkline, History, Run};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn get_mock_history() -> History {
+        let run = |revision: &str, nanos: f64| Run {
+            revision: revision.into(),
+            date: "2024-12-16".into(),
+            timestamp: 1_734_307_200,
+            timings: Timings {
+                data: vec![Timing {
+                    day: day!(16),
+                    part_1: Some("1.0ms".into()),
+                    part_2: None,
+                    total_nanos: nanos,
+                    alloc: [None, None],
+                }],
+            },
+        };
+
+        History {
+            runs: vec![
+                run("abc1234", 4e6),
+                run("def5678", 8e6),
+                run("0123abc", 1e6),
+            ],
+        }
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(1_734_307_200), "2024-12-16");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+    }
+
+    #[test]
+    fn renders_sparkline() {
+        assert_eq!(sparkline(&[1.0, 8.0, 4.5]), "▁█▅");
+        assert_eq!(sparkline(&[2.0, 2.0]), "▁▁");
+    }
+
+    #[test]
+    fn reports_best_and_worst_runs() {
+        let report = render_report(&get_mock_history(), &[day!(16)]);
+        assert!(report.contains("best:   1.0ms (0123abc"));
+        assert!(report.contains("worst:  8.0ms (def5678"));
+        assert!(report.contains("since first of 3 runs"));
+    }
+
+    #[test]
+    fn renders_svg_chart() {
+        let svg = render_svg(&get_mock_history(), &[day!(16)]);
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert!(svg.contains("Day 16"));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let json = tinyjson::JsonValue::from(get_mock_history())
+            .stringify()
+            .unwrap();
+        let history = History::try_from(json).unwrap();
+        assert_eq!(history.runs.len(), 3);
+        assert_eq!(history.runs[1].revision, "def5678");
+        assert_eq!(history.series(day!(16)).len(), 3);
+    }
+}
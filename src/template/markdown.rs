@@ -0,0 +1,348 @@
+/// Renders the markdown of downloaded puzzle descriptions to the terminal with ANSI styles.
+///
+/// Covers what aoc-cli writes: setext and ATX headings, paragraphs, lists, fenced code blocks,
+/// emphasis, inline code, links, backslash escapes and the `<em>` tags puzzles use for highlights.
+use crate::template::{ANSI_BOLD, ANSI_DIM, ANSI_GREEN, ANSI_RESET, ANSI_YELLOW};
+
+/// Whether a description includes part two, which is only served once part one is solved.
+pub fn has_part_two(markdown: &str) -> bool {
+    markdown.replace('\\', "").contains("--- Part Two ---")
+}
+
+/// Renders markdown, wrapping paragraphs to `width` columns.
+pub fn render(markdown: &str, width: usize) -> String {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut out = vec![];
+    let mut in_code_block = false;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        i += 1;
+
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            out.push(format!("    {ANSI_GREEN}{}{ANSI_RESET}", code_line(line)));
+            continue;
+        }
+
+        let trimmed = line.trim();
+
+        // setext headings are underlined by the next line.
+        let underline = lines.get(i).map(|l| l.trim());
+        if !trimmed.is_empty()
+            && underline.is_some_and(|u| {
+                u.len() >= 3 && (u.chars().all(|c| c == '-') || u.chars().all(|c| c == '='))
+            })
+        {
+            i += 1;
+            out.push(heading(trimmed));
+            continue;
+        }
+
+        if let Some(text) = trimmed.strip_prefix('#') {
+            out.push(heading(text.trim_start_matches('#').trim()));
+            continue;
+        }
+
+        if let Some(item) = trimmed
+            .strip_prefix("* ")
+            .or_else(|| trimmed.strip_prefix("- "))
+        {
+            out.extend(wrap(&inline(item), width, "  • ", "    "));
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            out.push(String::new());
+        } else {
+            out.extend(wrap(&inline(trimmed), width, "", ""));
+        }
+    }
+
+    // collapse runs of blank lines, e.g. around headings.
+    out.dedup_by(|a, b| a.is_empty() && b.is_empty());
+    let mut rendered = out.join("\n");
+    rendered.push('\n');
+    rendered
+}
+
+fn heading(text: &str) -> String {
+    format!("{ANSI_BOLD}{}{ANSI_RESET}", strip_styles(&inline(text)))
+}
+
+/// Text of a code block: entities are decoded and `<em>` highlights are kept.
+fn code_line(line: &str) -> String {
+    let highlighted = line
+        .replace("<em>", ANSI_BOLD)
+        .replace("</em>", &format!("{ANSI_RESET}{ANSI_GREEN}"))
+        .replace("<code>", "")
+        .replace("</code>", "");
+    decode_entities(&highlighted)
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    emphasis: bool,
+    star: bool,
+    code: bool,
+}
+
+impl Style {
+    /// Escape sequences that switch from any style to this one.
+    fn codes(self) -> String {
+        let mut codes = ANSI_RESET.to_string();
+        if self.code {
+            codes.push_str(ANSI_GREEN);
+        }
+        if self.star {
+            codes.push_str(ANSI_YELLOW);
+        }
+        if self.emphasis || self.star {
+            codes.push_str(ANSI_BOLD);
+        }
+        codes
+    }
+}
+
+/// Renders the inline elements of a line.
+fn inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut style = Style::default();
+    let mut rest = text;
+
+    let set = |out: &mut String, style: &mut Style, new: Style| {
+        if *style != new {
+            *style = new;
+            out.push_str(&style.codes());
+        }
+    };
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(tail) = rest.strip_prefix("<em class=\"star\">") {
+            let new = Style {
+                star: true,
+                ..style
+            };
+            set(&mut out, &mut style, new);
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("<em>") {
+            let new = Style {
+                emphasis: true,
+                ..style
+            };
+            set(&mut out, &mut style, new);
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("</em>") {
+            let new = Style {
+                emphasis: false,
+                star: false,
+                ..style
+            };
+            set(&mut out, &mut style, new);
+            rest = tail;
+        } else if let Some(tail) = rest
+            .strip_prefix("<code>")
+            .or_else(|| rest.strip_prefix("</code>"))
+        {
+            rest = tail;
+        } else if c == '\\' && rest.len() > 1 {
+            let escaped = rest[1..].chars().next().unwrap_or_default();
+            out.push(escaped);
+            rest = &rest[1 + escaped.len_utf8()..];
+        } else if c == '`' {
+            let new = Style {
+                code: !style.code,
+                ..style
+            };
+            set(&mut out, &mut style, new);
+            rest = &rest[1..];
+        } else if c == '*' && !style.code {
+            let tail = rest.trim_start_matches('*');
+            let new = Style {
+                emphasis: !style.emphasis,
+                ..style
+            };
+            set(&mut out, &mut style, new);
+            rest = tail;
+        } else if let Some((label, url, tail)) = (c == '[' && !style.code)
+            .then(|| parse_link(rest))
+            .flatten()
+        {
+            out.push_str(&inline(label));
+            out.push_str(&format!("{ANSI_DIM} ({url}){ANSI_RESET}"));
+            out.push_str(&style.codes());
+            rest = tail;
+        } else if c == '&' {
+            let end = rest
+                .find(';')
+                .filter(|end| *end <= 5)
+                .map_or(1, |end| end + 1);
+            out.push_str(&decode_entities(&rest[..end]));
+            rest = &rest[end..];
+        } else {
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    if style != Style::default() {
+        out.push_str(ANSI_RESET);
+    }
+    out
+}
+
+/// Parses `[label](url)` at the start of the text, returning the label, url and the rest.
+fn parse_link(text: &str) -> Option<(&str, &str, &str)> {
+    let (label, rest) = text.strip_prefix('[')?.split_once("](")?;
+    let (url, rest) = rest.split_once(')')?;
+    (!label.contains('[')).then_some((label, url, rest))
+}
+
+/// Returns the escape sequences of a string, in order.
+fn escape_sequences(s: &str) -> impl Iterator<Item = &str> {
+    s.match_indices('\x1b').filter_map(|(start, _)| {
+        let end = s[start..].find('m')?;
+        Some(&s[start..=start + end])
+    })
+}
+
+fn strip_styles(s: &str) -> String {
+    let mut stripped = s.to_string();
+    for sequence in escape_sequences(s) {
+        stripped = stripped.replacen(sequence, "", 1);
+    }
+    stripped
+}
+
+fn visible_width(s: &str) -> usize {
+    strip_styles(s).chars().count()
+}
+
+/// Wraps styled text at spaces. Styles that are active at a line break are carried over to the next line.
+fn wrap(text: &str, width: usize, first_indent: &str, indent: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = first_indent.to_string();
+    let mut line_width = first_indent.chars().count();
+    let mut is_empty = true;
+    let mut active = String::new();
+
+    for word in text.split(' ') {
+        let word_width = visible_width(word);
+
+        if !is_empty && line_width + 1 + word_width > width {
+            if !active.is_empty() {
+                line.push_str(ANSI_RESET);
+            }
+            lines.push(line);
+            line = format!("{indent}{active}");
+            line_width = indent.chars().count();
+            is_empty = true;
+        }
+
+        if !is_empty {
+            line.push(' ');
+            line_width += 1;
+        }
+        line.push_str(word);
+        line_width += word_width;
+        is_empty = false;
+
+        for sequence in escape_sequences(word) {
+            if sequence == ANSI_RESET {
+                active.clear();
+            } else {
+                active.push_str(sequence);
+            }
+        }
+    }
+
+    lines.push(line);
+    lines
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{has_part_two, inline, render, strip_styles, wrap};
+    use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RESET};
+
+    const PUZZLE: &str = "\\--- Day 1: Historian Hysteria ---
+----------
+
+The *Chief Historian* is always present for the big Christmas sleigh launch.
+
+For example:
+
+```
+3   4
+<em>4</em>   3
+```
+
+* Item `a &lt; b`
+* See [this page](/2024/about)
+
+Your puzzle answer was `11`.
+";
+
+    #[test]
+    fn renders_headings_and_blocks() {
+        let rendered = strip_styles(&render(PUZZLE, 80));
+        assert_eq!(
+            rendered,
+            "--- Day 1: Historian Hysteria ---
+
+The Chief Historian is always present for the big Christmas sleigh launch.
+
+For example:
+
+    3   4
+    4   3
+
+  • Item a < b
+  • See this page (/2024/about)
+
+Your puzzle answer was 11.
+"
+        );
+    }
+
+    #[test]
+    fn styles_inline_elements() {
+        assert_eq!(
+            inline("a *b* `c`"),
+            format!("a {ANSI_RESET}{ANSI_BOLD}b{ANSI_RESET} {ANSI_RESET}{ANSI_GREEN}c{ANSI_RESET}")
+        );
+        assert_eq!(inline("\\*not em\\*"), "*not em*");
+    }
+
+    #[test]
+    fn carries_styles_over_line_breaks() {
+        let text = format!("aaa {ANSI_BOLD}bbb ccc{ANSI_RESET} ddd");
+        let lines = wrap(&text, 8, "", "");
+        assert_eq!(
+            lines,
+            vec![
+                format!("aaa {ANSI_BOLD}bbb{ANSI_RESET}"),
+                format!("{ANSI_BOLD}ccc{ANSI_RESET} ddd"),
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_part_two() {
+        assert!(!has_part_two(PUZZLE));
+        assert!(has_part_two("\\--- Part Two ---\n----------"));
+    }
+}
@@ -1,19 +1,36 @@
 use std::{env, fs};
 
+pub mod alloc;
 pub mod aoc_cli;
 pub mod commands;
+pub mod differential;
+pub mod fuzz;
+pub mod rng;
 pub mod runner;
+pub mod scale;
 
 pub use day::*;
 
+mod cache;
 mod day;
+mod history;
+mod markdown;
 mod readme_benchmarks;
 mod run_multi;
+mod status;
+mod submissions;
+mod submit;
 mod timings;
+#[cfg(feature = "today")]
+mod unlock;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_DIM: &str = "\x1b[2m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
@@ -39,30 +56,103 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Alternatively, alternative implementations of a part can be registered as `variants`.
+/// They are run after the main implementation, and their results and timings are compared to it:
+///
+/// ```ignore
+/// advent_of_code::solution!(7, variants {
+///     part_one => [part_one_reverse],
+///     part_two => [part_two_reverse, part_two_naive],
+/// });
+/// ```
+///
+/// A scaler, which synthesizes larger inputs for `cargo scale`, can be registered as well:
+///
+/// ```ignore
+/// advent_of_code::solution!(1, scale = advent_of_code::template::scale::repeat_lines);
+/// ```
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, scale = $scaler:expr)? $(,)?) => {
+        $crate::solution!(@impl $day, [$($scaler)?], [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [], [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [], [part_two, 2]);
     };
+    ($day:expr, variants { $($variants:tt)* } $(, scale = $scaler:expr)? $(,)?) => {
+        $crate::solution!(@setup $day);
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        fn main() {
+            use $crate::template::runner::*;
+            $crate::solution!(@modes [$($scaler)?], [part_one, 1] [part_two, 2]);
+            let input = $crate::template::read_file("inputs", DAY);
+            $crate::solution!(@part &input, part_one, 1, $($variants)*);
+            $crate::solution!(@part &input, part_two, 2, $($variants)*);
+        }
+    };
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+    (@impl $day:expr, [$($scaler:expr)?], $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
 
         fn main() {
             use $crate::template::runner::*;
+            $crate::solution!(@modes [$($scaler)?], $( [$func, $part] )*);
             let input = $crate::template::read_file("inputs", DAY);
             $( run_part($func, &input, DAY, $part); )*
         }
     };
+
+    // when invoked with `--fuzz` or `--scale`, runs the parts on mutated or scaled inputs instead of solving.
+    (@modes [$($scaler:expr)?], $( [$func:expr, $part:expr] )*) => {
+        let parts: &[Part] = &[
+            $( ($part, |input: &str| $func(input).map(|result| result.to_string())), )*
+        ];
+        if $crate::template::fuzz::is_requested() {
+            $crate::template::fuzz::run(DAY, parts);
+            return;
+        }
+        if $crate::template::scale::is_requested() {
+            $crate::template::scale::run(DAY, parts, $crate::solution!(@scaler $($scaler)?));
+            return;
+        }
+    };
+    (@scaler) => { None };
+    (@scaler $scaler:expr) => { Some($scaler as $crate::template::scale::Scaler) };
+
+    (@setup $day:expr) => {
+        /// The current day.
+        const DAY: $crate::template::Day = $crate::day!($day);
+
+        #[cfg(feature = "dhat-heap")]
+        #[global_allocator]
+        static ALLOC: dhat::Alloc = dhat::Alloc;
+
+        #[cfg(all(feature = "alloc-stats", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
+    };
+
+    // runs a main implementation, then the variants registered for it.
+    (@part $input:expr, $main:ident, $part:expr, $($variants:tt)*) => {
+        let reference = run_part($main, $input, DAY, $part);
+        let mut variants = Variants::new(stringify!($main), reference);
+        $crate::solution!(@variants variants, $input, $main, $($variants)*);
+        variants.finish();
+    };
+
+    // NOTE: `macro_rules` cannot compare two identifiers, so each part is matched literally.
+    (@variants $v:ident, $input:expr, part_one, part_one => [$($variant:ident),* $(,)?] $(, $($rest:tt)*)?) => {
+        $( $v.run(stringify!($variant), $variant, $input); )*
+    };
+    (@variants $v:ident, $input:expr, part_two, part_two => [$($variant:ident),* $(,)?] $(, $($rest:tt)*)?) => {
+        $( $v.run(stringify!($variant), $variant, $input); )*
+    };
+    (@variants $v:ident, $input:expr, $main:ident, $other:ident => [$($variant:ident),* $(,)?] $(, $($rest:tt)*)?) => {
+        $crate::solution!(@variants $v, $input, $main, $($($rest)*)?);
+    };
+    (@variants $v:ident, $input:expr, $main:ident, $(,)?) => {};
 }
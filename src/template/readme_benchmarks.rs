@@ -1,11 +1,18 @@
-/// Module that updates the readme me with timing information.
+/// Module that updates the readme me with timing and progress information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fs, io, path::Path};
 
+use crate::template::alloc::format_bytes;
+use crate::template::aoc_cli::{get_puzzle_path, get_year};
+use crate::template::submissions::Submissions;
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{all_days, Day};
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static PROGRESS_MARKER: &str = "<!--- progress table --->";
+
+/// All markers that delimit a section maintained by this module.
+static MARKERS: [&str; 2] = [MARKER, PROGRESS_MARKER];
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -25,53 +32,155 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
+/// Stars earned for a day, along with the puzzle title if the description has been downloaded.
+#[derive(Clone, Debug)]
+pub struct DayProgress {
+    pub day: Day,
+    pub title: Option<String>,
+    pub stars: [bool; 2],
+}
+
+impl DayProgress {
+    /// Builds the progress of every day that has been scaffolded or submitted.
+    pub fn collect(submissions: &Submissions) -> Vec<Self> {
+        all_days()
+            .filter(|day| {
+                Path::new(&get_path_for_bin(*day)).exists()
+                    || submissions.data.iter().any(|s| s.day == *day)
+            })
+            .map(|day| DayProgress {
+                day,
+                title: fs::read_to_string(get_puzzle_path(day))
+                    .ok()
+                    .and_then(|md| parse_puzzle_title(&md)),
+                stars: [
+                    submissions.correct(day, 1).is_some(),
+                    submissions.correct(day, 2).is_some(),
+                ],
+            })
+            .collect()
+    }
+}
+
+/// Extracts the title from a puzzle description, e.g. `## \-\-\- Day 1: Historian Hysteria \-\-\-`.
+pub fn parse_puzzle_title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let line = line.replace('\\', "");
+        let (_, rest) = line.split_once("--- Day ")?;
+        let (_, title) = rest.split_once(':')?;
+        let title = title.trim().trim_end_matches('-').trim();
+        (!title.is_empty()).then(|| title.to_string())
+    })
+}
+
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Positions of the markers standing on their own line: a marker quoted in prose does not
+/// delimit a section. The line may hold both markers of an empty section.
+fn marker_indices(readme: &str, marker: &str) -> Vec<usize> {
+    let mut indices = vec![];
+    let mut offset = 0;
+    for line in readme.split_inclusive('\n') {
+        if line.contains(marker) && line.split(marker).all(|rest| rest.trim().is_empty()) {
+            indices.extend(line.match_indices(marker).map(|(i, _)| offset + i));
+        }
+        offset += line.len();
+    }
+    indices
+}
+
+/// Whether the readme has a section delimited by `marker`.
+fn has_section(readme: &str, marker: &str) -> bool {
+    !marker_indices(readme, marker).is_empty()
+}
+
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches = marker_indices(readme, marker);
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
     let pos_start = matches
         .first()
-        .map(|m| m.0)
+        .copied()
         .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
 
     let pos_end = matches
         .last()
-        .map(|m| m.0 + m.1.len())
+        .map(|i| i + marker.len())
         .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
 
+    // a section must not contain the markers of another section, otherwise replacing it would
+    // swallow (or split) the other one.
+    if let Some(other) = MARKERS.iter().filter(|m| **m != marker).find(|m| {
+        marker_indices(readme, m)
+            .iter()
+            .any(|i| (pos_start..pos_end).contains(i))
+    }) {
+        return Err(Error::Parser(format!(
+            "{marker}: section overlaps with the {other} section."
+        )));
+    }
+
     Ok(TablePosition { pos_start, pos_end })
 }
 
+/// Replaces the section delimited by `marker` with `table`, which includes its own markers.
+fn replace_section(s: &mut String, marker: &str, table: &str) -> Result<(), Error> {
+    let positions = locate_table(s, marker)?;
+    s.replace_range(positions.pos_start..positions.pos_end, table);
+    Ok(())
+}
+
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    // allocation columns are only rendered if at least one day was benched with `--alloc`.
+    let with_alloc = timings
+        .data
+        .iter()
+        .any(|t| t.alloc.iter().any(Option::is_some));
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if with_alloc {
+        lines.push("| Day | Part 1 | Part 2 | Part 1 heap | Part 2 heap |".into());
+        lines.push("| :---: | :---: | :---:  | :---: | :---: |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
+        let mut line = format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        );
+
+        if with_alloc {
+            for alloc in timing.alloc {
+                line.push_str(&match alloc {
+                    Some(alloc) => format!(
+                        " `{}` / `{}` peak |",
+                        format_bytes(alloc.bytes),
+                        format_bytes(alloc.peak)
+                    ),
+                    None => " `-` |".into(),
+                });
+            }
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
@@ -81,26 +190,91 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
+fn construct_progress_table(prefix: &str, progress: &[DayProgress], year: Option<u16>) -> String {
+    let header = format!("{prefix} Progress");
+
+    let mut lines: Vec<String> = vec![
+        PROGRESS_MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Puzzle | Part 1 | Part 2 |".into(),
+        "| :---: | :--- | :---: | :---: |".into(),
+    ];
+
+    let star = |earned: bool| if earned { "⭐" } else { " " };
+
+    for day in progress {
+        let title = match (&day.title, year) {
+            (Some(title), Some(year)) => format!(
+                "[{title}](https://adventofcode.com/{year}/day/{})",
+                day.day.into_inner()
+            ),
+            (Some(title), None) => title.clone(),
+            (None, _) => "-".into(),
+        };
+
+        lines.push(format!(
+            "| [Day {}]({}) | {} | {} | {} |",
+            day.day.into_inner(),
+            get_path_for_bin(day.day),
+            title,
+            star(day.stars[0]),
+            star(day.stars[1]),
+        ));
+    }
+
+    let stars = progress
+        .iter()
+        .flat_map(|day| day.stars)
+        .filter(|earned| *earned)
+        .count();
+
+    lines.push(String::new());
+    lines.push(format!("**Stars: {stars}/50**"));
+    lines.push(PROGRESS_MARKER.into());
+
+    lines.join("\n")
+}
+
 fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
     let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+    replace_section(s, MARKER, &table)
+}
+
+fn update_progress_content(
+    s: &mut String,
+    progress: &[DayProgress],
+    year: Option<u16>,
+) -> Result<(), Error> {
+    let table = construct_progress_table("##", progress, year);
+    replace_section(s, PROGRESS_MARKER, &table)
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(timings: Timings, submissions: &Submissions) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
     update_content(&mut readme, timings, total_millis)?;
+
+    // the progress table is optional, only update it if the readme has a section for it.
+    if has_section(&readme, PROGRESS_MARKER) {
+        let progress = DayProgress::collect(submissions);
+        update_progress_content(&mut readme, &progress, get_year())?;
+    }
+
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{
+        has_section, parse_puzzle_title, update_content, update_progress_content, DayProgress,
+        MARKER, PROGRESS_MARKER,
+    };
+    use crate::{
+        day, template::alloc::AllocStats, template::timings::Timing, template::timings::Timings,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -110,18 +284,21 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    alloc: [None, None],
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    alloc: [None, None],
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    alloc: [None, None],
                 },
             ],
         }
@@ -180,4 +357,98 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    fn get_mock_progress() -> Vec<DayProgress> {
+        vec![
+            DayProgress {
+                day: day!(1),
+                title: Some("Historian Hysteria".into()),
+                stars: [true, true],
+            },
+            DayProgress {
+                day: day!(2),
+                title: None,
+                stars: [true, false],
+            },
+        ]
+    }
+
+    #[test]
+    fn format_alloc_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].alloc[0] = Some(AllocStats {
+            allocations: 4,
+            bytes: 2048,
+            peak: 1024,
+        });
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Part 1 heap | Part 2 heap |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `2.0KiB` / `1.0KiB` peak | `-` |"
+        ));
+    }
+
+    #[test]
+    fn parses_puzzle_title() {
+        let md = "## \\-\\-\\- Day 1: Historian Hysteria \\-\\-\\-\n\nThe *Chief Historian*...";
+        assert_eq!(parse_puzzle_title(md).unwrap(), "Historian Hysteria");
+        assert!(parse_puzzle_title("# readme").is_none());
+    }
+
+    #[test]
+    fn format_progress() {
+        let mut s = format!("foo\n{}\n{}\nbaz", PROGRESS_MARKER, PROGRESS_MARKER);
+        update_progress_content(&mut s, &get_mock_progress(), Some(2024)).unwrap();
+        let expected = [
+            "foo",
+            "<!--- progress table --->",
+            "## Progress",
+            "",
+            "| Day | Puzzle | Part 1 | Part 2 |",
+            "| :---: | :--- | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | [Historian Hysteria](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](./src/bin/02.rs) | - | ⭐ |   |",
+            "",
+            "**Stars: 3/50**",
+            "<!--- progress table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn updates_both_sections() {
+        let mut s = format!("{MARKER}{MARKER}\nfoo\n{PROGRESS_MARKER}{PROGRESS_MARKER}");
+        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_progress_content(&mut s, &get_mock_progress(), None).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_progress_content(&mut s, &get_mock_progress(), None).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches(PROGRESS_MARKER).count(), 2);
+        assert_eq!(s.matches("## Benchmarks").count(), 1);
+        assert_eq!(s.matches("## Progress").count(), 1);
+        assert!(s.contains("\nfoo\n"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_sections_overlap() {
+        let mut s = format!("{MARKER}{PROGRESS_MARKER}{MARKER}{PROGRESS_MARKER}");
+        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+    }
+
+    #[test]
+    fn ignores_markers_quoted_in_prose() {
+        let prose = format!("The table between the `{PROGRESS_MARKER}` markers is generated.");
+        assert!(!has_section(&prose, PROGRESS_MARKER));
+
+        let mut s = format!("foo\n{PROGRESS_MARKER}\n{PROGRESS_MARKER}\n{prose}\n");
+        assert!(has_section(&s, PROGRESS_MARKER));
+        update_progress_content(&mut s, &get_mock_progress(), None).unwrap();
+        update_progress_content(&mut s, &get_mock_progress(), None).unwrap();
+        assert_eq!(s.matches("## Progress").count(), 1);
+        assert!(s.ends_with(&format!("{PROGRESS_MARKER}\n{prose}\n")));
+    }
 }
@@ -1,49 +1,201 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, ANSI_BOLD, ANSI_DIM, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    cache::{CacheEntry, KeyBuilder, ResultCache},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// How results are read from and written to the result cache.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Always run, don't store results. Used for benchmarks.
+    Off,
+    /// Reuse results of unchanged days, store the others.
+    Use,
+    /// Always run, store results.
+    Refresh,
+}
+
+/// Results of running several days.
+pub struct Runs {
+    pub timings: Timings,
+    /// Answers of the days that were solved, in day order.
+    pub answers: Vec<(Day, [Option<String>; 2])>,
+    /// Days whose results were served from the cache.
+    pub cached: Vec<Day>,
+}
+
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    with_alloc_stats: bool,
+    jobs: usize,
+    cache_mode: CacheMode,
+) -> Option<Runs> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut answers = vec![];
+    let mut cached_days = vec![];
+
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let mut cache = ResultCache::default();
+    let mut keys = HashMap::new();
+    if cache_mode != CacheMode::Off {
+        cache = ResultCache::read_from_file();
+        let key_builder = KeyBuilder::new(&profile(is_release, is_timed, with_alloc_stats));
+        keys = days
+            .iter()
+            .filter_map(|day| Some((*day, key_builder.key(*day)?)))
+            .collect();
+    }
+
+    let cached: HashMap<Day, Vec<String>> = if cache_mode == CacheMode::Use {
+        keys.iter()
+            .filter_map(|(day, key)| Some((*day, cache.get(*day, key)?.output.clone())))
+            .collect()
+    } else {
+        HashMap::new()
+    };
 
     let mut need_space = false;
+    let mut print_header = |day: Day| {
+        if need_space {
+            println!();
+        }
+        need_space = true;
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    };
+
+    let mut record = |day: Day, output: child_commands::SolutionOutput| {
+        if output.cached {
+            println!("{ANSI_DIM}(cached){ANSI_RESET}");
+            cached_days.push(day);
+        }
+
+        if output.stdout.is_empty() {
+            println!("Not solved.");
+            return;
+        }
+
+        let val = child_commands::parse_exec_time(&output.stdout, day);
+        timings.push(val);
+
+        let day_answers = child_commands::parse_answers(&output.stdout);
+        answers.push((day, day_answers.clone()));
+
+        // failed runs are not cached, e.g. a panic in part two after part one succeeded.
+        if output.success && !output.cached {
+            if let Some(key) = keys.get(&day) {
+                cache.insert(CacheEntry {
+                    day,
+                    key: key.clone(),
+                    answers: day_answers,
+                    output: output.stdout,
+                });
             }
-            need_space = true;
+        }
+    };
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let from_cache = |day: &Day| {
+        cached.get(day).map(|lines| child_commands::SolutionOutput {
+            stdout: lines.clone(),
+            success: true,
+            cached: true,
+            ..Default::default()
+        })
+    };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    if jobs > 1 {
+        // build once up front, so the concurrent `cargo run` invocations don't compete for the build.
+        if let Err(e) = child_commands::build_solutions(is_release, with_alloc_stats) {
+            eprintln!("Failed to build solutions: {e:?}");
+            return None;
+        }
 
-            if output.is_empty() {
-                println!("Not solved.");
+        child_commands::run_solutions_parallel(
+            &days,
+            jobs,
+            |day| {
+                from_cache(&day).unwrap_or_else(|| {
+                    child_commands::capture_solution(day, is_timed, is_release, with_alloc_stats)
+                })
+            },
+            |day, output| {
+                print_header(day);
+                for line in &output.stdout {
+                    println!("{line}");
+                }
+                for line in &output.stderr {
+                    eprintln!("{line}");
+                }
+                record(day, output);
+            },
+        );
+    } else {
+        for day in days {
+            print_header(day);
+            let output = if let Some(output) = from_cache(&day) {
+                for line in &output.stdout {
+                    println!("{line}");
+                }
+                output
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+                child_commands::run_solution(day, is_timed, is_release, with_alloc_stats).unwrap()
+            };
+            record(day, output);
+        }
+    }
+
+    if cache_mode != CacheMode::Off {
+        if let Err(e) = cache.store_file() {
+            eprintln!("Failed to store the result cache: {e}");
+        }
+    }
+
+    if !cached_days.is_empty() {
+        let days: Vec<String> = cached_days.iter().map(ToString::to_string).collect();
+        println!(
+            "\n{ANSI_DIM}Served from cache: day(s) {}. Use `--force` to rerun them.{ANSI_RESET}",
+            days.join(", ")
+        );
+    }
+
+    let timings = Timings { data: timings };
 
     if is_timed {
-        let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
     }
+
+    Some(Runs {
+        timings,
+        answers,
+        cached: cached_days,
+    })
+}
+
+/// Describes how solutions are built and run, part of the cache key.
+fn profile(is_release: bool, is_timed: bool, with_alloc_stats: bool) -> String {
+    let mut profile = String::from(if is_release { "release" } else { "debug" });
+    if is_timed {
+        profile.push_str("+timed");
+    }
+    if with_alloc_stats {
+        profile.push_str("+alloc-stats");
+    }
+    profile
 }
 
 #[allow(dead_code)]
@@ -68,39 +220,65 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{alloc::AllocStats, Day, ANSI_BOLD, ANSI_RESET};
     use std::{
-        io::{BufRead, BufReader},
+        collections::BTreeMap,
+        io::{self, BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
+        sync::{mpsc, Mutex},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    /// Arguments of the `cargo` invocation that runs the solution bin for a given day.
+    fn solution_args(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        with_alloc_stats: bool,
+    ) -> Vec<String> {
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            day.to_string(),
+        ];
 
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
+        }
+
+        if with_alloc_stats {
+            args.push("--features".into());
+            args.push("alloc-stats".into());
         }
 
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push("--".into());
+            args.push("--time".into());
+        }
+
+        args
+    }
+
+    /// Run the solution bin for a given day
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        with_alloc_stats: bool,
+    ) -> Result<SolutionOutput, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            return Ok(SolutionOutput::default());
         }
 
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
         let mut cmd = Command::new("cargo")
-            .args(&args)
+            .args(solution_args(day, is_timed, is_release, with_alloc_stats))
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -123,9 +301,155 @@ pub mod child_commands {
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
+
+        Ok(SolutionOutput {
+            stdout: output,
+            success: status.success(),
+            ..Default::default()
+        })
+    }
+
+    /// Builds all solution bins, with the same profile and features as [`run_solution`].
+    pub fn build_solutions(is_release: bool, with_alloc_stats: bool) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet", "--bins"];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        if with_alloc_stats {
+            args.push("--features");
+            args.push("alloc-stats");
+        }
+
+        let status = Command::new("cargo").args(&args).status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::IO(io::Error::other(format!(
+                "cargo build exited with {status}"
+            ))))
+        }
+    }
+
+    /// Output of a solution bin. When streamed to the terminal, `stderr` is not kept.
+    #[derive(Default)]
+    pub struct SolutionOutput {
+        pub stdout: Vec<String>,
+        pub stderr: Vec<String>,
+        /// Whether the bin exited successfully.
+        pub success: bool,
+        /// Whether the output was served from the result cache.
+        pub cached: bool,
+    }
+
+    pub fn capture_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        with_alloc_stats: bool,
+    ) -> SolutionOutput {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            return SolutionOutput::default();
+        }
+
+        let output = Command::new("cargo")
+            .args(solution_args(day, is_timed, is_release, with_alloc_stats))
+            .output();
+
+        match output {
+            Ok(output) => SolutionOutput {
+                success: output.status.success(),
+                cached: false,
+                stdout: String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .map(ToString::to_string)
+                    .collect(),
+                stderr: String::from_utf8_lossy(&output.stderr)
+                    .lines()
+                    .map(ToString::to_string)
+                    .collect(),
+            },
+            Err(e) => SolutionOutput {
+                stderr: vec![format!("Failed to run solution: {e}")],
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Runs `job` for the given days on `jobs` threads, e.g. [`capture_solution`]. Outputs are
+    /// handed to `on_output` in the order of `days` as soon as all previous days are done.
+    pub fn run_solutions_parallel(
+        days: &[Day],
+        jobs: usize,
+        job: impl Fn(Day) -> SolutionOutput + Sync,
+        mut on_output: impl FnMut(Day, SolutionOutput),
+    ) {
+        let queue = Mutex::new(days.iter().copied().enumerate());
+        let (tx, rx) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs.min(days.len()) {
+                let tx = tx.clone();
+                let (queue, job) = (&queue, &job);
+                scope.spawn(move || loop {
+                    let Some((index, day)) = queue.lock().unwrap().next() else {
+                        break;
+                    };
+                    if tx.send((index, day, job(day))).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(tx);
+
+            let mut pending = BTreeMap::new();
+            let mut next = 0;
+
+            for (index, day, output) in rx {
+                pending.insert(index, (day, output));
+                while let Some((day, output)) = pending.remove(&next) {
+                    on_output(day, output);
+                    next += 1;
+                }
+            }
+        });
+    }
+
+    /// Parses the answers of both parts, e.g. `42` from `Part 1: 42 (1.0ms)`.
+    /// Multi-line answers are not printed on the same line, and are returned as `▼`.
+    pub fn parse_answers(output: &[String]) -> [Option<String>; 2] {
+        let mut answers = [None, None];
+
+        for line in output {
+            // benched lines are printed over the intermediate result.
+            let line = line.rsplit('\r').next().unwrap_or_default();
+            let line = line.replace(ANSI_BOLD, "").replace(ANSI_RESET, "");
+
+            let Some(rest) = line.strip_prefix("Part ") else {
+                continue;
+            };
+            let Some((part, rest)) = rest.split_once(": ") else {
+                continue;
+            };
+            let index = match part {
+                "1" => 0,
+                "2" => 1,
+                _ => continue,
+            };
+
+            let answer = rest
+                .rsplit_once(" (")
+                .map_or(rest, |(answer, _)| answer)
+                .trim();
+            if !answer.is_empty() && answer != "✖" {
+                answers[index] = Some(answer.to_string());
+            }
+        }
 
-        Ok(output)
+        answers
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -134,6 +458,7 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            alloc: [None, None],
         };
 
         output
@@ -149,13 +474,18 @@ pub mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing_str, nanos, parse_alloc_stats(l)))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, nanos, alloc)| {
+                // other lines with timings, e.g. of variants, do not count towards the total.
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.alloc[0] = alloc;
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.alloc[1] = alloc;
+                } else {
+                    return;
                 }
 
                 timings.total_nanos += nanos;
@@ -164,6 +494,12 @@ pub mod child_commands {
         timings
     }
 
+    /// Parses the allocation stats that follow the timing, e.g. `(1ms @ 10 samples) [1 allocs, 8B total, 8B peak]`.
+    fn parse_alloc_stats(line: &str) -> Option<AllocStats> {
+        let (_, rest) = line.rsplit_once(" samples)")?;
+        AllocStats::parse(rest.trim().strip_prefix('[')?.strip_suffix(']')?)
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -173,8 +509,8 @@ pub mod child_commands {
         let str_timing = line
             .split(" samples)")
             .next()?
-            .split('(')
-            .last()?
+            .rsplit('(')
+            .next()?
             .split('@')
             .next()?
             .trim();
@@ -205,7 +541,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answers, parse_exec_time};
 
         use crate::day;
 
@@ -239,6 +575,52 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_alloc_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [3 allocs, 1.5KiB total, 512B peak]"
+                        .into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            let alloc = res.alloc[0].unwrap();
+            assert_eq!(alloc.allocations, 3);
+            assert_eq!(alloc.bytes, 1536);
+            assert_eq!(alloc.peak, 512);
+            assert!(res.alloc[1].is_none());
+        }
+
+        #[test]
+        fn ignores_variant_timings() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 42 (2ms @ 5 samples)".into(),
+                    "  ↳ part_one_naive: 42 (8ms @ 5 samples) 0.25x vs part_one".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 2000000_f64);
+            assert_eq!(res.part_1.unwrap(), "2ms");
+        }
+
+        #[test]
+        fn parses_answers() {
+            let answers = parse_answers(&[
+                "Part 1: \x1b[1m42\x1b[0m > benching\rPart 1: \x1b[1m42\x1b[0m (1.0ms @ 9 samples)"
+                    .into(),
+                "  ↳ part_one_naive: 41 (2.0ms) 0.50x vs part_one".into(),
+                "Part 2: ✖             ".into(),
+            ]);
+            assert_eq!(answers, [Some("42".into()), None]);
+
+            let answers = parse_answers(&["Part 2: 6,5 (12.0µs)".into()]);
+            assert_eq!(answers, [None, Some("6,5".into())]);
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
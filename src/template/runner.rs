@@ -6,41 +6,148 @@ use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::alloc::AllocStats;
+use crate::template::submissions::{Submission, Submissions, Verdict};
+use crate::template::{aoc_cli, submit, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{ANSI_BOLD, ANSI_RED};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// A solution part, with its result converted to a string. Used by the `fuzz` and `scale` modes.
+pub type Part = (u8, fn(&str) -> Option<String>);
+
+/// The outcome of running a solution part.
+pub struct PartRun {
+    pub result: Option<String>,
+    pub duration: Duration,
+    pub samples: u128,
+}
+
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+) -> PartRun {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
+    let (result, duration, samples, alloc_stats) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let mut duration_str = format_duration(&duration, samples);
+    if let Some(alloc_stats) = alloc_stats {
+        duration_str.push_str(&format!(" [{alloc_stats}]"));
+    }
+
+    print_result(&result, &part_str, &duration_str);
+
+    let run = PartRun {
+        result: result.as_ref().map(ToString::to_string),
+        duration,
+        samples,
+    };
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
+
+    run
+}
+
+/// Runs the alternative implementations of a part and compares them against the main one.
+pub struct Variants {
+    main: &'static str,
+    reference: PartRun,
+    disagreements: Vec<&'static str>,
+}
+
+impl Variants {
+    pub fn new(main: &'static str, reference: PartRun) -> Self {
+        Variants {
+            main,
+            reference,
+            disagreements: vec![],
+        }
+    }
+
+    /// Runs a variant, printing its result, timing and speedup relative to the main implementation.
+    ///
+    /// NOTE: variant lines must not start with `Part`, so they are not picked up as timings of the day.
+    pub fn run<I: Clone, T: Display>(
+        &mut self,
+        name: &'static str,
+        func: impl Fn(I) -> Option<T>,
+        input: I,
+    ) {
+        let label = format!("  ↳ {name}");
+
+        let (result, duration, samples, _) = run_timed(func, input, |_| print!("{label}: …"));
+        let result = result.map(|r| r.to_string());
+
+        let agrees = result == self.reference.result;
+        if !agrees {
+            self.disagreements.push(name);
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let speedup = self.reference.duration.as_nanos() as f64 / duration.as_nanos().max(1) as f64;
+
+        let result_str = match &result {
+            Some(result) if result.contains('\n') => "▼".into(),
+            Some(result) => result.clone(),
+            None => "✖".into(),
+        };
+
+        print!("\r");
+        println!(
+            "{label}: {}{result_str}{ANSI_RESET}{} {ANSI_ITALIC}{speedup:.2}x vs {}{ANSI_RESET}",
+            if agrees { ANSI_BOLD } else { ANSI_RED },
+            format_duration(&duration, samples),
+            self.main,
+        );
+    }
+
+    /// Reports whether all variants agree with the main implementation.
+    pub fn finish(self) {
+        if self.disagreements.is_empty() {
+            return;
+        }
+
+        eprintln!(
+            "{ANSI_RED}Variants disagree with {}: {}{ANSI_RESET}",
+            self.main,
+            self.disagreements.join(", ")
+        );
+    }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// With the `alloc-stats` feature, heap usage of the first execution is returned as well.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<AllocStats>) {
+    let input_clone = input.clone();
+
+    #[cfg(feature = "alloc-stats")]
+    crate::template::alloc::reset();
+
     let timer = Instant::now();
     let result = {
-        let input = input.clone();
-
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        func(input_clone)
     };
     let base_time = timer.elapsed();
 
+    #[cfg(feature = "alloc-stats")]
+    let alloc_stats = Some(crate::template::alloc::snapshot());
+    #[cfg(not(feature = "alloc-stats"))]
+    let alloc_stats = None;
+
     hook(&result);
 
     let run = if std::env::args().any(|x| x == "--time") {
@@ -49,7 +156,7 @@ fn run_timed<I: Clone, T>(
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, alloc_stats)
 }
 
 fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
@@ -130,8 +237,11 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  1. the answer passes the checks of [`submit::validate`], unless `--force` is passed.
+///  2. we are in `--release` mode, or the submission of a debug build is confirmed.
+///  3. aoc-cli is installed.
+///
+/// With `--retry`, answers sent too soon after the previous one are resubmitted once the server allows it.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
@@ -159,11 +269,51 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    let answer = result.to_string();
+
+    if !args.contains(&"--force".into()) {
+        let examples = submit::read_example_answers(day, part);
+        let submissions = Submissions::read_from_file();
+        if let Err(rejection) = submit::validate(day, part, &answer, &examples, &submissions) {
+            eprintln!("{ANSI_RED}Not submitting, {rejection}.{ANSI_RESET} Append --force to submit anyway.");
+            process::exit(1);
+        }
+    }
+
+    if cfg!(debug_assertions)
+        && !submit::confirm("This solution was built without --release, submit anyway?")
+    {
+        eprintln!("Not submitting, append --release to submit without confirmation.");
+        process::exit(1);
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let retry = args.contains(&"--retry".into());
+
+    loop {
+        println!("Submitting result via aoc-cli...");
+        let output = aoc_cli::submit(day, part, &answer);
+
+        let Ok(stdout) = output.as_ref().map(|o| String::from_utf8_lossy(&o.stdout)) else {
+            return Some(output);
+        };
+
+        let verdict = Verdict::parse(&stdout);
+        if let Err(e) = Submissions::record(Submission::new(day, part, &answer, verdict)) {
+            eprintln!("Failed to record submission: {e}");
+        }
+
+        match (verdict, submit::parse_wait(&stdout)) {
+            (Verdict::TooRecent, Some(wait)) if retry => submit::countdown(wait),
+            (Verdict::TooRecent, Some(wait)) => {
+                println!("Wait {wait:?} before submitting again, or append --retry to do it automatically.");
+                return Some(output);
+            }
+            _ => return Some(output),
+        }
+    }
 }
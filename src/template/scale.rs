@@ -0,0 +1,248 @@
+/// Complexity measurements: inputs are synthesized at 2×, 4×, 8×… the size of the real input, each
+/// part is timed on every size, and an empirical growth exponent is fitted to the timings.
+///
+/// Runs inside a day binary when it is invoked with `--scale`, which `cargo scale` does for every day.
+use std::{
+    env,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::template::{runner::Part, Day, ANSI_BOLD, ANSI_DIM, ANSI_RESET};
+
+/// Synthesizes an input `factor` times as large as the given one, in the dimension that drives
+/// the running time of the day (lines, grid size, length of records…).
+pub type Scaler = fn(&str, usize) -> String;
+
+/// Each size is run repeatedly until this much time has been spent, to smooth out fast runs.
+const MIN_SAMPLE_TIME: Duration = Duration::from_millis(100);
+
+pub fn is_requested() -> bool {
+    env::args().any(|x| x == "--scale")
+}
+
+/// A scaler for inputs made of independent lines, which are repeated.
+pub fn repeat_lines(input: &str, factor: usize) -> String {
+    (0..factor)
+        .flat_map(|_| input.lines())
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
+
+/// A scaler that repeats every line in place, e.g. `a\nb` becomes `a\na\nb\nb` at 2×.
+pub fn repeat_each_line(input: &str, factor: usize) -> String {
+    input
+        .lines()
+        .flat_map(|line| std::iter::repeat_n(line, factor))
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
+
+pub struct Options {
+    /// Largest scale factor to run.
+    pub max_factor: usize,
+    /// Larger sizes of a part are skipped once a run takes longer than this.
+    pub budget: Duration,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            max_factor: 16,
+            budget: Duration::from_secs(5),
+        }
+    }
+}
+
+impl Options {
+    /// Reads the options passed by `cargo scale` to the day binary.
+    fn from_args() -> Self {
+        let mut args = pico_args::Arguments::from_env();
+        let default = Options::default();
+
+        let max_factor = args.opt_value_from_str("--max-factor").ok().flatten();
+        let budget = args.opt_value_from_str("--budget").ok().flatten();
+
+        Options {
+            max_factor: max_factor.unwrap_or(default.max_factor),
+            budget: budget.map_or(default.budget, Duration::from_millis),
+        }
+    }
+
+    fn factors(&self) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(Some(1usize), |f| f.checked_mul(2))
+            .take_while(|f| *f <= self.max_factor)
+    }
+}
+
+/// Fits `time = c * factor^k` to the measurements with least squares on a log-log scale, and returns `k`.
+/// Returns `None` with less than two measurements.
+pub fn fit_exponent(measurements: &[(usize, Duration)]) -> Option<f64> {
+    #[allow(clippy::cast_precision_loss)]
+    let points: Vec<(f64, f64)> = measurements
+        .iter()
+        .filter(|(_, d)| !d.is_zero())
+        .map(|(f, d)| ((*f as f64).ln(), d.as_secs_f64().ln()))
+        .collect();
+
+    if points.len() < 2 {
+        return None;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    Some(covariance / variance)
+}
+
+/// Describes a growth exponent, e.g. `~quadratic` for 2.1.
+pub fn describe_exponent(exponent: f64) -> &'static str {
+    match exponent {
+        e if e < 0.5 => "~constant",
+        e if e < 1.3 => "~linear",
+        e if e < 1.8 => "superlinear",
+        e if e < 2.5 => "~quadratic",
+        _ => "cubic or worse",
+    }
+}
+
+/// Runs a part on an input, repeatedly if it is fast, and returns the average duration of a run.
+fn measure(func: fn(&str) -> Option<String>, input: &str) -> (Option<String>, Duration) {
+    let timer = Instant::now();
+    let result = black_box(func(black_box(input)));
+    let mut runs = 1;
+
+    while timer.elapsed() < MIN_SAMPLE_TIME {
+        black_box(func(black_box(input)));
+        runs += 1;
+    }
+
+    (result, timer.elapsed() / runs)
+}
+
+/// Times every part of a day on scaled inputs, and prints a line with the fitted exponent of each part.
+///
+/// These lines, e.g. `Part 1: ~n^2.01 (1.2s @ 16x)`, are parsed by `cargo scale`.
+pub fn run(day: Day, parts: &[Part], scaler: Option<Scaler>) {
+    let Some(scaler) = scaler else {
+        println!("No scaler registered, see `solution!(…, scale = …)`.");
+        return;
+    };
+
+    let options = Options::from_args();
+
+    // prefer the real input, examples are too small to say much about growth.
+    let base = std::fs::read_to_string(format!("data/inputs/{day}.txt"))
+        .or_else(|_| std::fs::read_to_string(format!("data/examples/{day}.txt")));
+    let Ok(base) = base else {
+        println!("No input or example to scale.");
+        return;
+    };
+
+    let inputs: Vec<(usize, String)> = options
+        .factors()
+        .map(|factor| {
+            let input = if factor == 1 {
+                base.clone()
+            } else {
+                scaler(&base, factor)
+            };
+            (factor, input)
+        })
+        .collect();
+
+    for &(part, func) in parts {
+        println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+
+        let mut measurements = vec![];
+        for (factor, input) in &inputs {
+            let (result, duration) = measure(func, input);
+            if result.is_none() {
+                println!("  {ANSI_DIM}not solved.{ANSI_RESET}");
+                break;
+            }
+
+            println!(
+                "  {factor:>4}x: {duration:.1?} {ANSI_DIM}({} bytes){ANSI_RESET}",
+                input.len()
+            );
+            measurements.push((*factor, duration));
+
+            if duration > options.budget {
+                println!("  {ANSI_DIM}skipping larger sizes, over budget.{ANSI_RESET}");
+                break;
+            }
+        }
+
+        if let (Some(exponent), Some((factor, duration))) =
+            (fit_exponent(&measurements), measurements.last())
+        {
+            println!("Part {part}: ~n^{exponent:.2} ({duration:.1?} @ {factor}x)");
+        }
+    }
+}
+
+/// Parses a line printed by [`run`], returning the part, exponent and largest measurement.
+pub fn parse_summary(line: &str) -> Option<(u8, f64, String)> {
+    let rest = line.strip_prefix("Part ")?;
+    let (part, rest) = rest.split_once(": ~n^")?;
+    let (exponent, largest) = rest.split_once(' ')?;
+    let largest = largest.strip_prefix('(')?.strip_suffix(')')?;
+    Some((
+        part.parse().ok()?,
+        exponent.parse().ok()?,
+        largest.to_string(),
+    ))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{describe_exponent, fit_exponent, parse_summary, repeat_each_line, repeat_lines};
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn fits_exponents() {
+        let linear = [1, 2, 4, 8].map(|f| (f, Duration::from_millis(3 * f as u64)));
+        assert!(approx(fit_exponent(&linear).unwrap(), 1.0));
+
+        let quadratic = [1, 2, 4, 8].map(|f| (f, Duration::from_micros(5 * (f * f) as u64)));
+        assert!(approx(fit_exponent(&quadratic).unwrap(), 2.0));
+
+        assert_eq!(fit_exponent(&[(1, Duration::from_millis(1))]), None);
+    }
+
+    #[test]
+    fn describes_exponents() {
+        assert_eq!(describe_exponent(0.1), "~constant");
+        assert_eq!(describe_exponent(1.05), "~linear");
+        assert_eq!(describe_exponent(2.01), "~quadratic");
+    }
+
+    #[test]
+    fn repeats_lines() {
+        assert_eq!(repeat_lines("a\nb\n", 2), "a\nb\na\nb\n");
+        assert_eq!(repeat_each_line("a\nb\n", 2), "a\na\nb\nb\n");
+    }
+
+    #[test]
+    fn parses_summaries() {
+        assert_eq!(
+            parse_summary("Part 2: ~n^2.01 (1.2s @ 16x)"),
+            Some((2, 2.01, "1.2s @ 16x".into()))
+        );
+        assert_eq!(parse_summary("Part 2: 42 (1.2s)"), None);
+    }
+}
@@ -0,0 +1,445 @@
+/// Collects the progress of every day of advent: scaffolded files, submitted answers,
+/// example tests and stored benchmarks.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufRead, BufReader},
+    path::Path,
+    process::{Command, Stdio},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    aoc_cli::{get_input_path, get_puzzle_path},
+    run_multi::get_path_for_bin,
+    submissions::{Submissions, Verdict},
+    timings::Timings,
+    Day, ANSI_BOLD, ANSI_DIM, ANSI_GREEN, ANSI_RED, ANSI_RESET, ANSI_YELLOW,
+};
+
+/// Whether a file of a day exists and has content.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileState {
+    Missing,
+    Empty,
+    Present,
+}
+
+impl FileState {
+    fn of(path: &Path) -> Self {
+        match fs::metadata(path) {
+            Ok(meta) if meta.len() > 0 => FileState::Present,
+            Ok(_) => FileState::Empty,
+            Err(_) => FileState::Missing,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            FileState::Missing => "missing",
+            FileState::Empty => "empty",
+            FileState::Present => "present",
+        }
+    }
+}
+
+/// The outcome of the example test of a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestState {
+    Passed,
+    Failed,
+    /// The test binary ran but has no test for this part.
+    Missing,
+    /// Tests were not run.
+    Skipped,
+}
+
+impl TestState {
+    fn as_str(self) -> &'static str {
+        match self {
+            TestState::Passed => "passed",
+            TestState::Failed => "failed",
+            TestState::Missing => "missing",
+            TestState::Skipped => "skipped",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PartStatus {
+    pub answer: Option<String>,
+    pub verdict: Option<Verdict>,
+    pub test: TestState,
+    pub timing: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DayStatus {
+    pub day: Day,
+    pub source: FileState,
+    pub input: FileState,
+    pub examples: FileState,
+    pub puzzle: FileState,
+    pub parts: [PartStatus; 2],
+}
+
+impl DayStatus {
+    /// Gathers the status of a day from disk. Example tests are only run if `run_tests` is set.
+    pub fn collect(
+        day: Day,
+        submissions: &Submissions,
+        timings: &Timings,
+        run_tests: bool,
+    ) -> Self {
+        let source = FileState::of(Path::new(&get_path_for_bin(day)));
+
+        let tests = if run_tests && source == FileState::Present {
+            run_example_tests(day)
+        } else {
+            [TestState::Skipped, TestState::Skipped]
+        };
+
+        let timing = timings.data.iter().find(|t| t.day == day);
+
+        let part = |part: u8| {
+            let submission = submissions.latest(day, part);
+            PartStatus {
+                answer: submission.map(|s| s.answer.clone()),
+                verdict: submission.map(|s| s.verdict),
+                test: tests[usize::from(part - 1)],
+                timing: timing.and_then(|t| {
+                    if part == 1 {
+                        t.part_1.clone()
+                    } else {
+                        t.part_2.clone()
+                    }
+                }),
+            }
+        };
+
+        DayStatus {
+            day,
+            source,
+            input: FileState::of(Path::new(&get_input_path(day))),
+            examples: examples_state(day),
+            puzzle: FileState::of(Path::new(&get_puzzle_path(day))),
+            parts: [part(1), part(2)],
+        }
+    }
+}
+
+/// Examples are either stored as `NN.txt` or, for days with several examples, as `NN-X.txt`.
+fn examples_state(day: Day) -> FileState {
+    let Ok(entries) = fs::read_dir("data/examples") else {
+        return FileState::Missing;
+    };
+
+    let prefix = day.to_string();
+
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.strip_suffix(".txt").is_some_and(|stem| {
+                stem == prefix
+                    || stem
+                        .strip_prefix(&prefix)
+                        .is_some_and(|rest| rest.starts_with('-'))
+            })
+        })
+        .map(|entry| FileState::of(&entry.path()))
+        .max_by_key(|state| match state {
+            FileState::Missing => 0,
+            FileState::Empty => 1,
+            FileState::Present => 2,
+        })
+        .unwrap_or(FileState::Missing)
+}
+
+/// Runs the unit tests of a day binary and reports the outcome of its `part_one` / `part_two` tests.
+fn run_example_tests(day: Day) -> [TestState; 2] {
+    let day_padded = day.to_string();
+
+    let Ok(mut cmd) = Command::new("cargo")
+        .args(["test", "--bin", &day_padded, "--", "--test-threads=1"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return [TestState::Skipped, TestState::Skipped];
+    };
+
+    let lines: Vec<String> = cmd
+        .stdout
+        .take()
+        .map(|stdout| {
+            BufReader::new(stdout)
+                .lines()
+                .map_while(Result::ok)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let _ = cmd.wait();
+
+    parse_test_output(&lines)
+}
+
+/// Parses the libtest output, e.g. `test tests::test_part_one ... ok`.
+fn parse_test_output(lines: &[String]) -> [TestState; 2] {
+    let mut states = [TestState::Missing, TestState::Missing];
+
+    for line in lines {
+        let Some(test) = line.strip_prefix("test ") else {
+            continue;
+        };
+
+        let Some((name, result)) = test.split_once(" ... ") else {
+            continue;
+        };
+
+        let index = if name.contains("part_one") {
+            0
+        } else if name.contains("part_two") {
+            1
+        } else {
+            continue;
+        };
+
+        states[index] = match (states[index], result.trim()) {
+            (TestState::Failed, _) | (_, "FAILED") => TestState::Failed,
+            (_, "ok") => TestState::Passed,
+            (state, _) => state,
+        };
+    }
+
+    states
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Width of the answer column of each part.
+const ANSWER_WIDTH: usize = 16;
+/// Width of a part column: answer, verdict, test and timing.
+const PART_WIDTH: usize = ANSWER_WIDTH + 1 + 12 + 1 + 4 + 1 + 9;
+
+/// Pads `text` to `width` before coloring it, so escape codes don't break the alignment.
+fn colored(text: &str, width: usize, color: &str) -> String {
+    format!("{color}{text:<width$}{ANSI_RESET}")
+}
+
+fn file_cell(state: FileState) -> String {
+    match state {
+        FileState::Present => colored("✓", 4, ANSI_GREEN),
+        FileState::Empty => colored("○", 4, ANSI_YELLOW),
+        FileState::Missing => colored("✗", 4, ANSI_RED),
+    }
+}
+
+fn part_cell(part: &PartStatus) -> String {
+    let answer = part.answer.as_deref().unwrap_or("-");
+    let answer = if answer.chars().count() > ANSWER_WIDTH {
+        let truncated: String = answer.chars().take(ANSWER_WIDTH - 1).collect();
+        format!("{truncated}…")
+    } else {
+        answer.to_string()
+    };
+
+    let verdict = match part.verdict {
+        Some(v) if v.is_correct() => colored("★", 12, ANSI_YELLOW),
+        Some(v) if v.is_wrong() => colored(&format!("✗ {v}"), 12, ANSI_RED),
+        Some(v) => colored(&v.to_string(), 12, ANSI_DIM),
+        None => colored("-", 12, ANSI_DIM),
+    };
+
+    let test = match part.test {
+        TestState::Passed => colored("pass", 4, ANSI_GREEN),
+        TestState::Failed => colored("fail", 4, ANSI_RED),
+        TestState::Missing | TestState::Skipped => colored("-", 4, ANSI_DIM),
+    };
+
+    let timing = part.timing.as_deref().unwrap_or("-");
+
+    format!("{ANSI_BOLD}{answer:<ANSWER_WIDTH$}{ANSI_RESET} {verdict} {test} {timing:<9}")
+}
+
+/// Renders the status of every day as a colored table for the terminal.
+pub fn render_table(statuses: &[DayStatus]) -> String {
+    let part_header = format!(
+        "{:<ANSWER_WIDTH$} {:<12} {:<4} {:<9}",
+        "answer", "verdict", "test", "time"
+    );
+
+    let mut lines = vec![
+        format!(
+            "{ANSI_BOLD}{:<20}| {:<PART_WIDTH$} | {}{ANSI_RESET}",
+            "Day src in  ex  pz", "Part 1", "Part 2"
+        ),
+        format!(
+            "{ANSI_DIM}{:<20}| {part_header} | {part_header}{ANSI_RESET}",
+            ""
+        ),
+    ];
+
+    for status in statuses {
+        if status.source == FileState::Missing {
+            lines.push(format!(
+                "{ANSI_DIM}{}  not scaffolded{ANSI_RESET}",
+                status.day
+            ));
+            continue;
+        }
+
+        lines.push(format!(
+            "{}  {}{}{}{}| {} | {}",
+            status.day,
+            file_cell(status.source),
+            file_cell(status.input),
+            file_cell(status.examples),
+            file_cell(status.puzzle),
+            part_cell(&status.parts[0]),
+            part_cell(&status.parts[1]),
+        ));
+    }
+
+    let stars = statuses
+        .iter()
+        .flat_map(|s| s.parts.iter())
+        .filter(|p| p.verdict.is_some_and(Verdict::is_correct))
+        .count();
+
+    lines.push(String::new());
+    lines.push(format!("{ANSI_BOLD}Stars:{ANSI_RESET} {stars}/50"));
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_string(value: Option<String>) -> JsonValue {
+    value.map_or(JsonValue::Null, JsonValue::String)
+}
+
+impl From<&PartStatus> for JsonValue {
+    fn from(value: &PartStatus) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("answer".into(), optional_string(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            optional_string(value.verdict.map(|v| v.to_string())),
+        );
+        map.insert("test".into(), JsonValue::String(value.test.as_str().into()));
+        map.insert("timing".into(), optional_string(value.timing.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&DayStatus> for JsonValue {
+    fn from(value: &DayStatus) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "source".into(),
+            JsonValue::String(value.source.as_str().into()),
+        );
+        map.insert(
+            "input".into(),
+            JsonValue::String(value.input.as_str().into()),
+        );
+        map.insert(
+            "examples".into(),
+            JsonValue::String(value.examples.as_str().into()),
+        );
+        map.insert(
+            "puzzle".into(),
+            JsonValue::String(value.puzzle.as_str().into()),
+        );
+        map.insert("part_1".into(), JsonValue::from(&value.parts[0]));
+        map.insert("part_2".into(), JsonValue::from(&value.parts[1]));
+
+        JsonValue::Object(map)
+    }
+}
+
+/// Renders the status of every day as a JSON document.
+pub fn render_json(statuses: &[DayStatus]) -> String {
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+    map.insert(
+        "data".into(),
+        JsonValue::Array(statuses.iter().map(JsonValue::from).collect()),
+    );
+
+    JsonValue::Object(map)
+        .format()
+        .unwrap_or_else(|_| "{}".into())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_test_output, render_json, DayStatus, FileState, PartStatus, TestState};
+    use crate::{day, template::submissions::Verdict};
+    use std::{collections::HashMap, str::FromStr};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn parses_test_output() {
+        let res = parse_test_output(&[
+            "running 2 tests".into(),
+            "test tests::test_part_one ... ok".into(),
+            "test tests::test_part_two ... FAILED".into(),
+            "".into(),
+        ]);
+        assert_eq!(res, [TestState::Passed, TestState::Failed]);
+    }
+
+    #[test]
+    fn parses_multiple_tests_per_part() {
+        let res = parse_test_output(&[
+            "test tests::test_part_one_example_1 ... FAILED".into(),
+            "test tests::test_part_one_example_2 ... ok".into(),
+            "test tests::test_helper ... ok".into(),
+        ]);
+        assert_eq!(res, [TestState::Failed, TestState::Missing]);
+    }
+
+    #[test]
+    fn serializes_status() {
+        let part = PartStatus {
+            answer: Some("42".into()),
+            verdict: Some(Verdict::Correct),
+            test: TestState::Passed,
+            timing: Some("1.2ms".into()),
+        };
+        let status = DayStatus {
+            day: day!(3),
+            source: FileState::Present,
+            input: FileState::Empty,
+            examples: FileState::Present,
+            puzzle: FileState::Missing,
+            parts: [
+                part,
+                PartStatus {
+                    answer: None,
+                    verdict: None,
+                    test: TestState::Skipped,
+                    timing: None,
+                },
+            ],
+        };
+
+        let json = JsonValue::from_str(&render_json(&[status])).unwrap();
+        let data: &Vec<_> = json["data"].get().unwrap();
+        let day: &HashMap<String, JsonValue> = data[0].get().unwrap();
+        assert_eq!(day["day"].get::<String>().unwrap(), "03");
+        assert_eq!(day["input"].get::<String>().unwrap(), "empty");
+        assert_eq!(day["part_1"]["verdict"].get::<String>().unwrap(), "correct");
+        assert!(day["part_2"]["answer"].is_null());
+    }
+}
@@ -0,0 +1,366 @@
+/// Keeps a record of every answer submitted through aoc-cli and the verdict the server returned.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.json";
+
+/// The answer of the server to a submission, as reported by aoc-cli.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    TooRecent,
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    /// Extracts the verdict from the text printed by `aoc submit`.
+    pub fn parse(output: &str) -> Self {
+        if output.contains("That's the right answer") {
+            Verdict::Correct
+        } else if output.contains("That's not the right answer") {
+            if output.contains("too high") {
+                Verdict::TooHigh
+            } else if output.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            }
+        } else if output.contains("You gave an answer too recently") {
+            Verdict::TooRecent
+        } else if output.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether the submitted answer has been accepted.
+    pub fn is_correct(self) -> bool {
+        self == Verdict::Correct
+    }
+
+    /// Whether the submitted answer has been rejected as wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooRecent => "too recent",
+            Verdict::WrongLevel => "wrong level",
+            Verdict::Unknown => "unknown",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too high" => Ok(Verdict::TooHigh),
+            "too low" => Ok(Verdict::TooLow),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "too recent" => Ok(Verdict::TooRecent),
+            "wrong level" => Ok(Verdict::WrongLevel),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// A single answer sent to the server.
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl Submission {
+    pub fn new(day: Day, part: u8, answer: &str, verdict: Verdict) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        Submission {
+            day,
+            part,
+            answer: answer.into(),
+            verdict,
+            timestamp,
+        }
+    }
+}
+
+/// All submissions made so far, in chronological order.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(SUBMISSIONS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns no submissions.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(SUBMISSIONS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Submissions::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Appends a submission to the file on disk.
+    pub fn record(submission: Submission) -> Result<(), Error> {
+        let mut submissions = Self::read_from_file();
+        submissions.data.push(submission);
+        submissions.store_file()
+    }
+
+    /// The most recent submission for a part of a day.
+    pub fn latest(&self, day: Day, part: u8) -> Option<&Submission> {
+        self.data
+            .iter()
+            .rev()
+            .find(|s| s.day == day && s.part == part)
+    }
+
+    /// The accepted submission for a part of a day, if any.
+    pub fn correct(&self, day: Day, part: u8) -> Option<&Submission> {
+        self.data
+            .iter()
+            .find(|s| s.day == day && s.part == part && s.verdict.is_correct())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|p| **p == 1.0 || **p == 2.0)
+            .map(|p| *p as u8)
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| Verdict::from_str(v).ok())
+            .ok_or("Expected submission.verdict to be a verdict.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|t| *t as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Submission, Submissions, Verdict};
+    use crate::day;
+
+    fn get_mock_submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                Submission {
+                    day: day!(1),
+                    part: 1,
+                    answer: "12".into(),
+                    verdict: Verdict::TooLow,
+                    timestamp: 10,
+                },
+                Submission {
+                    day: day!(1),
+                    part: 1,
+                    answer: "42".into(),
+                    verdict: Verdict::Correct,
+                    timestamp: 20,
+                },
+                Submission {
+                    day: day!(1),
+                    part: 1,
+                    answer: "42".into(),
+                    verdict: Verdict::WrongLevel,
+                    timestamp: 30,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::parse("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer. If you're stuck, ..."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::parse("You gave an answer too recently; you have to wait."),
+            Verdict::TooRecent
+        );
+        assert_eq!(Verdict::parse(""), Verdict::Unknown);
+    }
+
+    #[test]
+    fn finds_latest_and_correct_submissions() {
+        let submissions = get_mock_submissions();
+        assert_eq!(submissions.latest(day!(1), 1).unwrap().timestamp, 30);
+        assert_eq!(submissions.correct(day!(1), 1).unwrap().timestamp, 20);
+        assert!(submissions.latest(day!(1), 2).is_none());
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let json = tinyjson::JsonValue::from(get_mock_submissions())
+            .stringify()
+            .unwrap();
+        let submissions = Submissions::try_from(json).unwrap();
+        assert_eq!(submissions.data.len(), 3);
+        assert_eq!(submissions.data[0].answer, "12");
+        assert_eq!(submissions.data[0].verdict, Verdict::TooLow);
+        assert_eq!(submissions.data[2].part, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_submissions() {
+        let json = r#"{ "data": [{ "day": "01", "part": 3 }] }"#.to_string();
+        Submissions::try_from(json).unwrap();
+    }
+}
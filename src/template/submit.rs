@@ -0,0 +1,298 @@
+/// Safety checks run before an answer is sent to the server, and handling of its rate limit.
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    thread,
+    time::Duration,
+};
+
+use crate::template::{
+    submissions::{Submissions, Verdict},
+    Day,
+};
+
+/// Why an answer is not worth submitting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    Empty,
+    MultiLine,
+    /// Zero, negative, or the maximum of an integer type, which usually means an overflow or a sentinel.
+    Implausible,
+    /// The answer to the example, likely the example was solved instead of the input.
+    ExampleAnswer,
+    /// The same answer was submitted before.
+    AlreadySubmitted(Verdict),
+    /// The server said an earlier answer was too high (or too low), and this one is even higher (or lower).
+    OutOfBounds(Verdict, String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::Empty => write!(f, "the answer is empty"),
+            Rejection::MultiLine => write!(f, "the answer spans several lines"),
+            Rejection::Implausible => {
+                write!(f, "the answer looks like an overflow or a placeholder")
+            }
+            Rejection::ExampleAnswer => write!(f, "the answer is the one of the example"),
+            Rejection::AlreadySubmitted(verdict) => {
+                write!(f, "the answer was already submitted and was {verdict}")
+            }
+            Rejection::OutOfBounds(verdict, bound) => {
+                write!(f, "{bound} was already {verdict}")
+            }
+        }
+    }
+}
+
+/// Answers that are almost never right: zero, and the bounds of integer types.
+fn is_implausible(answer: &str) -> bool {
+    let maximums = [
+        u64::MAX.to_string(),
+        i64::MAX.to_string(),
+        u32::MAX.to_string(),
+        i32::MAX.to_string(),
+    ];
+    answer == "0" || answer.starts_with('-') || maximums.iter().any(|max| max == answer)
+}
+
+/// Checks an answer against the example answers and the previous submissions of a part.
+pub fn validate(
+    day: Day,
+    part: u8,
+    answer: &str,
+    examples: &[String],
+    submissions: &Submissions,
+) -> Result<(), Rejection> {
+    if answer.trim().is_empty() {
+        return Err(Rejection::Empty);
+    }
+    if answer.contains('\n') {
+        return Err(Rejection::MultiLine);
+    }
+    if is_implausible(answer) {
+        return Err(Rejection::Implausible);
+    }
+    if examples.iter().any(|example| example == answer) {
+        return Err(Rejection::ExampleAnswer);
+    }
+
+    let previous = submissions
+        .data
+        .iter()
+        .filter(|s| s.day == day && s.part == part);
+
+    for submission in previous {
+        if submission.answer == answer
+            && (submission.verdict.is_correct() || submission.verdict.is_wrong())
+        {
+            return Err(Rejection::AlreadySubmitted(submission.verdict));
+        }
+
+        let (Ok(answer), Ok(bound)) = (answer.parse::<i128>(), submission.answer.parse::<i128>())
+        else {
+            continue;
+        };
+
+        let out_of_bounds = match submission.verdict {
+            Verdict::TooHigh => answer >= bound,
+            Verdict::TooLow => answer <= bound,
+            _ => false,
+        };
+        if out_of_bounds {
+            return Err(Rejection::OutOfBounds(
+                submission.verdict,
+                submission.answer.clone(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Extracts the answers asserted by the example tests of a part, e.g. `11` from
+/// `assert_eq!(result, Some(11));` in `fn test_part_one()`.
+pub fn example_answers(source: &str, part: u8) -> Vec<String> {
+    let name = if part == 1 {
+        "fn test_part_one"
+    } else {
+        "fn test_part_two"
+    };
+
+    source
+        .match_indices(name)
+        .filter_map(|(start, _)| {
+            let body = &source[start + name.len()..];
+            // tests of other parts or helpers may follow, only look at this function.
+            let body = &body[..body.find("\n    fn ").unwrap_or(body.len())];
+            parse_literal(body.split_once("Some(")?.1)
+        })
+        .collect()
+}
+
+/// Parses the literal at the start of the text into the answer it displays as, e.g. `1_000u64)`
+/// into `1000`. Returns `None` for anything but a number or a string literal.
+fn parse_literal(text: &str) -> Option<String> {
+    if let Some(string) = text.strip_prefix('"') {
+        return Some(string.split_once('"')?.0.to_string());
+    }
+
+    let number: String = text
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '_' || *c == '-')
+        .filter(|c| *c != '_')
+        .collect();
+
+    (!number.is_empty()).then_some(number)
+}
+
+/// Reads the example answers of a day from its solution.
+pub fn read_example_answers(day: Day, part: u8) -> Vec<String> {
+    fs::read_to_string(format!("src/bin/{day}.rs"))
+        .map(|source| example_answers(&source, part))
+        .unwrap_or_default()
+}
+
+/// Parses the time left before answering again, e.g. from `You have 1m 30s left to wait.`
+pub fn parse_wait(output: &str) -> Option<Duration> {
+    let (_, rest) = output.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    let mut seconds = 0;
+    for amount in wait.split_whitespace() {
+        let unit = amount.chars().last()?;
+        let value: u64 = amount.strip_suffix(unit)?.parse().ok()?;
+        seconds += value
+            * match unit {
+                'h' => 3600,
+                'm' => 60,
+                's' => 1,
+                _ => return None,
+            };
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+/// Sleeps for the given time, showing the seconds left.
+pub fn countdown(wait: Duration) {
+    for left in (1..=wait.as_secs()).rev() {
+        print!("\rRetrying in {left}s... ");
+        let _ = io::stdout().flush();
+        thread::sleep(Duration::from_secs(1));
+    }
+    print!("\r                    \r");
+}
+
+/// Asks a yes/no question on the terminal. Defaults to no, also when there is no terminal to ask on.
+pub fn confirm(question: &str) -> bool {
+    if !io::stdin().is_terminal() {
+        return false;
+    }
+
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line).is_ok()
+        && matches!(line.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{example_answers, parse_wait, validate, Rejection};
+    use crate::{
+        day,
+        template::submissions::{Submission, Submissions, Verdict},
+    };
+
+    fn submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                Submission::new(day!(1), 1, "500", Verdict::TooHigh),
+                Submission::new(day!(1), 1, "100", Verdict::TooLow),
+                Submission::new(day!(1), 1, "300", Verdict::Incorrect),
+                Submission::new(day!(1), 1, "200", Verdict::TooRecent),
+            ],
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_answers() {
+        let check = |answer: &str| validate(day!(1), 1, answer, &[], &Submissions::default());
+        assert_eq!(check(""), Err(Rejection::Empty));
+        assert_eq!(check("1\n2"), Err(Rejection::MultiLine));
+        assert_eq!(check("0"), Err(Rejection::Implausible));
+        assert_eq!(check("-3"), Err(Rejection::Implausible));
+        assert_eq!(check("18446744073709551615"), Err(Rejection::Implausible));
+        assert_eq!(check("42"), Ok(()));
+        assert_eq!(check("6,0"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_example_answers() {
+        let examples = vec!["11".to_string()];
+        let result = validate(day!(1), 1, "11", &examples, &Submissions::default());
+        assert_eq!(result, Err(Rejection::ExampleAnswer));
+    }
+
+    #[test]
+    fn rejects_answers_out_of_known_bounds() {
+        let check = |answer: &str| validate(day!(1), 1, answer, &[], &submissions());
+        assert_eq!(
+            check("600"),
+            Err(Rejection::OutOfBounds(Verdict::TooHigh, "500".into()))
+        );
+        assert_eq!(
+            check("50"),
+            Err(Rejection::OutOfBounds(Verdict::TooLow, "100".into()))
+        );
+        assert_eq!(
+            check("300"),
+            Err(Rejection::AlreadySubmitted(Verdict::Incorrect))
+        );
+        // answers that were too recent never got a verdict.
+        assert_eq!(check("200"), Ok(()));
+        assert_eq!(validate(day!(1), 2, "600", &[], &submissions()), Ok(()));
+    }
+
+    #[test]
+    fn extracts_example_answers() {
+        let source = r#"
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(1_000u64));
+    }
+
+    #[test]
+    fn test_part_one_brute_force() {
+        assert_eq!(result, Some("6,1".to_string()));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+"#;
+        assert_eq!(example_answers(source, 1), vec!["1000", "6,1"]);
+        assert!(example_answers(source, 2).is_empty());
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        let output = "You gave an answer too recently; you have to wait after submitting an \
+            answer before trying again. You have 1m 30s left to wait.";
+        assert_eq!(parse_wait(output), Some(Duration::from_secs(90)));
+        assert_eq!(
+            parse_wait("You have 34s left to wait."),
+            Some(Duration::from_secs(34))
+        );
+        assert_eq!(parse_wait("That's the right answer!"), None);
+        assert_eq!(parse_wait("You have 5é left to wait."), None);
+    }
+}
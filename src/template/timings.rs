@@ -1,7 +1,7 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{alloc::AllocStats, Day};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
@@ -12,6 +12,8 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// Heap usage of each part, if benched with the `alloc-stats` feature.
+    pub alloc: [Option<AllocStats>; 2],
 }
 
 /// Represents benchmark times for a set of days.
@@ -51,7 +53,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -133,6 +135,13 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        // only written when present, so timings without allocation stats keep their format.
+        for (key, alloc) in ["part_1_alloc", "part_2_alloc"].iter().zip(value.alloc) {
+            if let Some(alloc) = alloc {
+                map.insert((*key).into(), JsonValue::from(alloc));
+            }
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,11 +175,61 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let alloc = ["part_1_alloc", "part_2_alloc"].map(|key| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => AllocStats::try_from(v).map(Some),
+        });
+        let [part_1_alloc, part_2_alloc] = alloc;
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            alloc: [part_1_alloc?, part_2_alloc?],
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<AllocStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+        map.insert("peak".into(), JsonValue::Number(value.peak as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected alloc stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let field = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("Expected alloc.{key} to be a number."))
+        };
+
+        Ok(AllocStats {
+            allocations: field("allocations")?,
+            bytes: field("bytes")?,
+            peak: field("peak")?,
         })
     }
 }
@@ -191,18 +250,21 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    alloc: [None, None],
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    alloc: [None, None],
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    alloc: [None, None],
                 },
             ],
         }
@@ -282,10 +344,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    alloc: [None, None],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -296,10 +359,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    alloc: [None, None],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -310,10 +374,11 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    alloc: [None, None],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -334,6 +399,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    alloc: [None, None],
                 }],
             };
             let merged = timings.merge(&other);
@@ -354,6 +420,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    alloc: [None, None],
                 }],
             };
             let merged = timings.merge(&other);
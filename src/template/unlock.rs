@@ -0,0 +1,240 @@
+/// Waiting for puzzles to unlock, at midnight in the timezone of the server, for `today --wait`.
+use std::{
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+use crate::template::{Day, SERVER_UTC_OFFSET};
+
+/// Longest time to wait between two download attempts.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Source of the current time, which tests replace to avoid waiting.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The clock of the system.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// When the puzzle of a day unlocks.
+pub fn unlock_time(year: i32, day: Day) -> Option<DateTime<Utc>> {
+    let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+    let unlock = offset
+        .with_ymd_and_hms(year, 12, u32::from(day.into_inner()), 0, 0, 0)
+        .single()?;
+    Some(unlock.with_timezone(&Utc))
+}
+
+/// The next puzzle to unlock after `now` in the given year, or `None` once all have been unlocked.
+pub fn next_unlock(now: DateTime<Utc>, year: i32) -> Option<(Day, DateTime<Utc>)> {
+    (1..=25)
+        .filter_map(Day::new)
+        .filter_map(|day| Some((day, unlock_time(year, day)?)))
+        .find(|(_, unlock)| *unlock > now)
+}
+
+/// The year of the event at the given time, in the timezone of the server.
+pub fn current_year(now: DateTime<Utc>) -> i32 {
+    let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).unwrap();
+    now.with_timezone(&offset).year()
+}
+
+/// Formats the time left as `1d 02:03:04`.
+fn format_remaining(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    let (days, hours, minutes, seconds) =
+        (secs / 86_400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+/// Waits until the given time, showing a countdown.
+pub fn wait_until(clock: &impl Clock, day: Day, unlock: DateTime<Utc>) {
+    while let Ok(remaining) = (unlock - clock.now()).to_std() {
+        if remaining.is_zero() {
+            break;
+        }
+
+        print!(
+            "\r⏳ Day {day} unlocks in {}   ",
+            format_remaining(remaining)
+        );
+        let _ = io::stdout().flush();
+
+        // sleep to the next whole second, so the countdown ticks in step with the clock.
+        let subsec = Duration::from_nanos(u64::from(remaining.subsec_nanos()));
+        clock.sleep(if subsec.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            subsec
+        });
+    }
+    println!("\r🔓 Day {day} is unlocked!                ");
+}
+
+/// Calls `attempt` until it succeeds, doubling the delay between attempts up to [`MAX_BACKOFF`].
+/// Returns the last error after `max_attempts` failed attempts.
+pub fn retry_with_backoff<T, E: std::fmt::Display>(
+    clock: &impl Clock,
+    max_attempts: usize,
+    mut attempt: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let mut delay = Duration::from_secs(1);
+
+    for attempts in 1.. {
+        match attempt() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempts >= max_attempts => return Err(e),
+            Err(e) => {
+                eprintln!("Attempt {attempts} failed: {e}. Retrying in {delay:?}...");
+                clock.sleep(delay);
+                delay = (delay * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+
+    unreachable!()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{cell::RefCell, time::Duration};
+
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use super::{
+        current_year, format_remaining, next_unlock, retry_with_backoff, unlock_time, wait_until,
+        Clock,
+    };
+    use crate::day;
+
+    /// A clock that only moves when slept on, and keeps track of the sleeps.
+    struct FakeClock {
+        now: RefCell<DateTime<Utc>>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn at(now: DateTime<Utc>) -> Self {
+            FakeClock {
+                now: RefCell::new(now),
+                sleeps: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            *self.now.borrow()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            *self.now.borrow_mut() += duration;
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    fn utc(month: u32, day: u32, hour: u32, minute: u32, second: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, month, day, hour, minute, second)
+            .unwrap()
+    }
+
+    #[test]
+    fn unlocks_at_midnight_on_the_server() {
+        assert_eq!(unlock_time(2024, day!(1)), Some(utc(12, 1, 5, 0, 0)));
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        assert_eq!(
+            next_unlock(utc(11, 15, 0, 0, 0), 2024),
+            Some((day!(1), utc(12, 1, 5, 0, 0)))
+        );
+        // just before midnight at UTC-5, which is already the next day in UTC.
+        assert_eq!(
+            next_unlock(utc(12, 6, 4, 59, 30), 2024),
+            Some((day!(6), utc(12, 6, 5, 0, 0)))
+        );
+        assert_eq!(
+            next_unlock(utc(12, 6, 5, 0, 0), 2024),
+            Some((day!(7), utc(12, 7, 5, 0, 0)))
+        );
+        assert_eq!(next_unlock(utc(12, 25, 5, 0, 1), 2024), None);
+        assert_eq!(current_year(utc(1, 1, 3, 0, 0)), 2023);
+    }
+
+    #[test]
+    fn waits_until_unlock() {
+        let clock = FakeClock::at(utc(12, 6, 4, 59, 57) - Duration::from_millis(500));
+        wait_until(&clock, day!(6), utc(12, 6, 5, 0, 0));
+
+        assert_eq!(clock.now(), utc(12, 6, 5, 0, 0));
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            vec![
+                Duration::from_millis(500),
+                Duration::from_secs(1),
+                Duration::from_secs(1),
+                Duration::from_secs(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn retries_with_backoff() {
+        let clock = FakeClock::at(utc(12, 6, 5, 0, 0));
+        let mut attempts = 0;
+
+        let result = retry_with_backoff(&clock, 10, || {
+            attempts += 1;
+            if attempts < 4 {
+                Err("not unlocked")
+            } else {
+                Ok(attempts)
+            }
+        });
+
+        assert_eq!(result, Ok(4));
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            [1, 2, 4].map(Duration::from_secs).to_vec()
+        );
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let clock = FakeClock::at(utc(12, 6, 5, 0, 0));
+        let result: Result<(), _> = retry_with_backoff(&clock, 8, || Err("not unlocked"));
+
+        assert_eq!(result, Err("not unlocked"));
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            [1, 2, 4, 8, 16, 32, 60].map(Duration::from_secs).to_vec()
+        );
+    }
+
+    #[test]
+    fn formats_remaining_time() {
+        assert_eq!(format_remaining(Duration::from_secs(59)), "00:00:59");
+        assert_eq!(format_remaining(Duration::from_secs(90_061)), "1d 01:01:01");
+    }
+}
@@ -0,0 +1,323 @@
+//! Matching many patterns at once, over byte strings.
+//!
+//! A [`Trie`] finds the patterns starting at an offset in a single walk, which is all it takes to
+//! count or list the ways to split a string into patterns. An [`AhoCorasick`] automaton finds
+//! every occurrence of the patterns, anywhere in a string, in a single pass.
+//!
+//! ```
+//! # use advent_of_code::trie::{AhoCorasick, Trie};
+//! let trie = Trie::from_patterns(["r", "wr", "b", "g", "bwu", "rb", "gb", "br"]);
+//! assert_eq!(trie.count_segmentations(b"gbbr"), 4);
+//! assert_eq!(trie.count_segmentations(b"ubwu"), 0);
+//!
+//! let automaton = AhoCorasick::new(trie);
+//! assert_eq!(automaton.find_overlapping(b"bwu").count(), 2);
+//! ```
+use std::collections::{BTreeMap, VecDeque};
+
+/// An occurrence of a pattern, at `start..end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Match {
+    /// The index of the pattern, in insertion order.
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Node {
+    children: BTreeMap<u8, usize>,
+    pattern: Option<usize>,
+}
+
+/// A set of patterns, stored as a tree of their prefixes. Empty patterns never match.
+#[derive(Clone, Debug)]
+pub struct Trie {
+    nodes: Vec<Node>,
+    lengths: Vec<usize>,
+}
+
+impl Trie {
+    pub fn new() -> Self {
+        Trie {
+            nodes: vec![Node::default()],
+            lengths: vec![],
+        }
+    }
+
+    pub fn from_patterns<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = P>) -> Self {
+        let mut trie = Trie::new();
+        for pattern in patterns {
+            trie.insert(pattern.as_ref());
+        }
+        trie
+    }
+
+    /// Adds a pattern, returning its index. A pattern added twice keeps its first index.
+    pub fn insert(&mut self, pattern: &[u8]) -> usize {
+        let mut node = 0;
+        for byte in pattern {
+            node = match self.nodes[node].children.get(byte) {
+                Some(child) => *child,
+                None => {
+                    self.nodes.push(Node::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.insert(*byte, child);
+                    child
+                }
+            };
+        }
+
+        *self.nodes[node].pattern.get_or_insert_with(|| {
+            self.lengths.push(pattern.len());
+            self.lengths.len() - 1
+        })
+    }
+
+    /// The number of patterns.
+    pub fn len(&self) -> usize {
+        self.lengths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lengths.is_empty()
+    }
+
+    /// The patterns starting at `start` in `text`, shortest first.
+    pub fn prefixes<'a>(
+        &'a self,
+        text: &'a [u8],
+        start: usize,
+    ) -> impl Iterator<Item = Match> + 'a {
+        let mut node = 0;
+        text[start..]
+            .iter()
+            .map_while(move |byte| {
+                node = *self.nodes[node].children.get(byte)?;
+                Some(node)
+            })
+            .zip(start + 1..)
+            .filter_map(move |(node, end)| {
+                let pattern = self.nodes[node].pattern?;
+                Some(Match {
+                    pattern,
+                    start,
+                    end,
+                })
+            })
+    }
+
+    /// Every occurrence of the patterns in `text`, by starting offset and then by length.
+    pub fn matches<'a>(&'a self, text: &'a [u8]) -> impl Iterator<Item = Match> + 'a {
+        (0..text.len()).flat_map(move |start| self.prefixes(text, start))
+    }
+
+    /// For each offset of `text`, the number of ways to split what follows into patterns.
+    fn suffix_segmentations(&self, text: &[u8]) -> Vec<u64> {
+        let mut ways = vec![0; text.len() + 1];
+        ways[text.len()] = 1;
+        for start in (0..text.len()).rev() {
+            ways[start] = self.prefixes(text, start).map(|m| ways[m.end]).sum();
+        }
+        ways
+    }
+
+    /// The number of ways to split `text` into a sequence of patterns.
+    pub fn count_segmentations(&self, text: &[u8]) -> u64 {
+        self.suffix_segmentations(text)[0]
+    }
+
+    /// Every way to split `text` into a sequence of patterns, as the indices of the patterns.
+    /// There can be exponentially many of them, see [`Trie::count_segmentations`].
+    pub fn segmentations(&self, text: &[u8]) -> Vec<Vec<usize>> {
+        let ways = self.suffix_segmentations(text);
+        let mut segmentations = vec![];
+        let mut stack = vec![(0, vec![])];
+
+        while let Some((start, patterns)) = stack.pop() {
+            if start == text.len() {
+                segmentations.push(patterns);
+                continue;
+            }
+            // only follow matches which lead to at least one segmentation.
+            for m in self.prefixes(text, start).filter(|m| ways[m.end] > 0) {
+                let mut patterns = patterns.clone();
+                patterns.push(m.pattern);
+                stack.push((m.end, patterns));
+            }
+        }
+
+        segmentations
+    }
+}
+
+impl Default for Trie {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A trie with the links needed to keep matching after a mismatch, to find every occurrence of
+/// the patterns in a single pass over a string.
+#[derive(Clone, Debug)]
+pub struct AhoCorasick {
+    trie: Trie,
+    /// The node of the longest proper suffix of a node which is also in the trie.
+    fail: Vec<usize>,
+    /// The node of the longest proper suffix of a node which is a pattern.
+    output: Vec<Option<usize>>,
+}
+
+impl AhoCorasick {
+    pub fn new(trie: Trie) -> Self {
+        let mut fail = vec![0; trie.nodes.len()];
+        let mut output = vec![None; trie.nodes.len()];
+
+        // breadth first, so that the links of shorter suffixes are known first.
+        let mut queue: VecDeque<usize> = trie.nodes[0].children.values().copied().collect();
+        while let Some(node) = queue.pop_front() {
+            for (byte, child) in &trie.nodes[node].children {
+                let mut suffix = fail[node];
+                let link = loop {
+                    if let Some(next) = trie.nodes[suffix].children.get(byte) {
+                        break *next;
+                    }
+                    if suffix == 0 {
+                        break 0;
+                    }
+                    suffix = fail[suffix];
+                };
+                fail[*child] = link;
+                output[*child] = match trie.nodes[link].pattern {
+                    Some(_) if link != 0 => Some(link),
+                    _ => output[link],
+                };
+                queue.push_back(*child);
+            }
+        }
+
+        AhoCorasick { trie, fail, output }
+    }
+
+    pub fn trie(&self) -> &Trie {
+        &self.trie
+    }
+
+    fn step(&self, mut node: usize, byte: u8) -> usize {
+        loop {
+            if let Some(next) = self.trie.nodes[node].children.get(&byte) {
+                return *next;
+            }
+            if node == 0 {
+                return 0;
+            }
+            node = self.fail[node];
+        }
+    }
+
+    /// Every occurrence of the patterns in `text`, overlapping ones included, by ending offset
+    /// and then from the longest.
+    pub fn find_overlapping<'a>(&'a self, text: &'a [u8]) -> impl Iterator<Item = Match> + 'a {
+        text.iter()
+            .zip(1..)
+            .scan(0, |node, (byte, end)| {
+                *node = self.step(*node, *byte);
+                Some((*node, end))
+            })
+            .flat_map(move |(node, end)| {
+                let first = match self.trie.nodes[node].pattern {
+                    Some(_) if node != 0 => Some(node),
+                    _ => self.output[node],
+                };
+                std::iter::successors(first, |n| self.output[*n]).map(move |n| {
+                    let pattern = self.trie.nodes[n].pattern.unwrap();
+                    Match {
+                        pattern,
+                        start: end - self.trie.lengths[pattern],
+                        end,
+                    }
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOWELS: [&str; 8] = ["r", "wr", "b", "g", "bwu", "rb", "gb", "br"];
+
+    #[test]
+    fn matches_prefixes() {
+        let mut trie = Trie::from_patterns(["he", "hers", "his", "she"]);
+        assert_eq!(trie.len(), 4);
+        assert_eq!(trie.insert(b"his"), 2);
+        assert_eq!(trie.insert(b""), 4);
+
+        let text = b"ushers";
+        let at = |start| -> Vec<usize> { trie.prefixes(text, start).map(|m| m.pattern).collect() };
+        assert_eq!(at(0), Vec::<usize>::new());
+        assert_eq!(at(1), vec![3]);
+        assert_eq!(at(2), vec![0, 1]);
+        assert_eq!(at(6), Vec::<usize>::new());
+
+        let matches: Vec<(usize, usize)> = trie.matches(text).map(|m| (m.start, m.end)).collect();
+        assert_eq!(matches, vec![(1, 4), (2, 4), (2, 6)]);
+    }
+
+    #[test]
+    fn splits_into_patterns() {
+        let trie = Trie::from_patterns(TOWELS);
+        let counts = [
+            "brwrr", "bggr", "gbbr", "rrbgbr", "ubwu", "bwurrg", "brgr", "bbrgwb",
+        ]
+        .map(|design| trie.count_segmentations(design.as_bytes()));
+        assert_eq!(counts, [2, 1, 4, 6, 0, 1, 2, 0]);
+        assert_eq!(trie.count_segmentations(b""), 1);
+
+        let mut splits: Vec<Vec<&str>> = trie
+            .segmentations(b"brwrr")
+            .into_iter()
+            .map(|s| s.into_iter().map(|p| TOWELS[p]).collect())
+            .collect();
+        splits.sort();
+        assert_eq!(
+            splits,
+            vec![vec!["b", "r", "wr", "r"], vec!["br", "wr", "r"]]
+        );
+        assert!(trie.segmentations(b"ubwu").is_empty());
+    }
+
+    #[test]
+    fn finds_overlapping_occurrences() {
+        let automaton = AhoCorasick::new(Trie::from_patterns(["he", "hers", "his", "she"]));
+        let matches: Vec<(usize, usize, usize)> = automaton
+            .find_overlapping(b"ushers ahishe")
+            .map(|m| (m.pattern, m.start, m.end))
+            .collect();
+        assert_eq!(
+            matches,
+            vec![
+                (3, 1, 4),
+                (0, 2, 4),
+                (1, 2, 6),
+                (2, 8, 11),
+                (3, 10, 13),
+                (0, 11, 13)
+            ]
+        );
+    }
+
+    #[test]
+    fn agrees_with_trie() {
+        let trie = Trie::from_patterns(["a", "ab", "bab", "bc", "bca", "c", "caa", "aaa"]);
+        let automaton = AhoCorasick::new(trie.clone());
+        let text = b"abccabbabcaaabcaacbcaaab";
+
+        let mut expected: Vec<Match> = trie.matches(text).collect();
+        let mut found: Vec<Match> = automaton.find_overlapping(text).collect();
+        expected.sort_by_key(|m| (m.start, m.end));
+        found.sort_by_key(|m| (m.start, m.end));
+        assert_eq!(found, expected);
+    }
+}
@@ -0,0 +1,299 @@
+//! Disjoint sets, and connectivity in graphs whose nodes are opened or blocked over time.
+//!
+//! Answering "when do these two nodes stop being connected?" by searching again after every
+//! change is slow. [`Timeline`] answers it with a single pass of union-find, going back in time
+//! so that nodes only ever get opened.
+//!
+//! ```
+//! # use advent_of_code::union_find::Timeline;
+//! // a path 0 - 1 - 2 - 3, where nodes get blocked in this order.
+//! let neighbours = |n: usize| [n.checked_sub(1), Some(n + 1).filter(|n| *n < 4)];
+//! let mut timeline = Timeline::new(4, |n| neighbours(n).into_iter().flatten());
+//! assert_eq!(timeline.first_disconnection(&[0, 2, 1], 1, 3), Some(1));
+//! assert_eq!(timeline.first_connection(&[2, 1], 0, 3), Some(1));
+//! ```
+use crate::geometry::{Direction, Position};
+
+/// A partition of `0..len` into disjoint sets, which can only be merged.
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// One set per element.
+    pub fn new(len: usize) -> Self {
+        UnionFind {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            sets: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The number of sets.
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+
+    /// The representative of the set of an element.
+    pub fn find(&mut self, mut x: usize) -> usize {
+        // path halving: every other element on the way points to its grandparent.
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merges the sets of two elements, returning whether they were separate.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.sets -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The size of the set of an element.
+    pub fn set_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+}
+
+/// A graph of nodes `0..nodes`, given by the neighbours of each node, for questions about when
+/// two nodes get connected or disconnected as nodes are opened or blocked.
+pub struct Timeline<F> {
+    nodes: usize,
+    neighbours: F,
+}
+
+impl<F, I> Timeline<F>
+where
+    F: FnMut(usize) -> I,
+    I: IntoIterator<Item = usize>,
+{
+    pub fn new(nodes: usize, neighbours: F) -> Self {
+        Timeline { nodes, neighbours }
+    }
+
+    /// Opens a node, connecting it to its open neighbours.
+    fn open(&mut self, sets: &mut UnionFind, open: &mut [bool], node: usize) {
+        open[node] = true;
+        for neighbour in (self.neighbours)(node) {
+            if open[neighbour] {
+                sets.union(node, neighbour);
+            }
+        }
+    }
+
+    /// With all nodes open at first, and then the nodes of `blocked` blocked one after another,
+    /// the index in `blocked` of the node whose blocking disconnects `a` from `b`. `None` if they
+    /// are still connected at the end, or never were.
+    pub fn first_disconnection(&mut self, blocked: &[usize], a: usize, b: usize) -> Option<usize> {
+        // only the first blocking of a node matters.
+        let mut blocked_at = vec![None; self.nodes];
+        for (i, node) in blocked.iter().enumerate() {
+            blocked_at[*node].get_or_insert(i);
+        }
+
+        // start from the end, with every blocked node blocked.
+        let mut sets = UnionFind::new(self.nodes);
+        let mut open = vec![false; self.nodes];
+        for (node, blocked_at) in blocked_at.iter().enumerate() {
+            if blocked_at.is_none() {
+                self.open(&mut sets, &mut open, node);
+            }
+        }
+        let connected =
+            |sets: &mut UnionFind, open: &[bool]| open[a] && open[b] && sets.connected(a, b);
+        if connected(&mut sets, &open) {
+            return None;
+        }
+
+        // and unblock nodes going back in time, until `a` and `b` are connected again.
+        for (i, node) in blocked.iter().enumerate().rev() {
+            if blocked_at[*node] != Some(i) {
+                continue;
+            }
+            self.open(&mut sets, &mut open, *node);
+            if connected(&mut sets, &open) {
+                return Some(i);
+            }
+        }
+
+        None
+    }
+
+    /// With all nodes blocked at first but `a` and `b`, and then the nodes of `opened` opened one
+    /// after another, the index in `opened` of the node whose opening connects `a` to `b`.
+    pub fn first_connection(&mut self, opened: &[usize], a: usize, b: usize) -> Option<usize> {
+        let mut sets = UnionFind::new(self.nodes);
+        let mut open = vec![false; self.nodes];
+        self.open(&mut sets, &mut open, a);
+        self.open(&mut sets, &mut open, b);
+
+        for (i, node) in opened.iter().enumerate() {
+            if !open[*node] {
+                self.open(&mut sets, &mut open, *node);
+            }
+            if sets.connected(a, b) {
+                return Some(i);
+            }
+        }
+
+        None
+    }
+}
+
+/// Numbers the cells of a grid of size `(width, height)` as nodes, row by row, followed by one
+/// node for each of the four borders around the grid.
+#[derive(Clone, Copy, Debug)]
+pub struct GridNodes {
+    width: usize,
+    height: usize,
+}
+
+impl GridNodes {
+    pub fn new((width, height): (usize, usize)) -> Self {
+        GridNodes { width, height }
+    }
+
+    /// The number of nodes, borders included.
+    pub fn count(&self) -> usize {
+        self.width * self.height + 4
+    }
+
+    pub fn cell(&self, position: Position) -> usize {
+        position.y * self.width + position.x
+    }
+
+    /// The position of a node, or `None` for a border.
+    pub fn position(&self, node: usize) -> Option<Position> {
+        (node < self.width * self.height)
+            .then(|| Position::new(node % self.width, node / self.width))
+    }
+
+    /// The node of the border on a side of the grid.
+    pub fn border(&self, side: Direction) -> usize {
+        self.width * self.height + side as usize
+    }
+
+    /// The cells next to a cell, in the four directions.
+    pub fn neighbours(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.position(node)
+            .into_iter()
+            .flat_map(move |p| p.neighbours((self.width, self.height)))
+            .map(|p| self.cell(p))
+    }
+
+    /// The cells next to a cell, in the eight directions.
+    pub fn neighbours8(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.position(node)
+            .into_iter()
+            .flat_map(move |p| p.neighbours8((self.width, self.height)))
+            .map(|p| self.cell(p))
+    }
+
+    /// The borders a cell touches, or the cells along a border.
+    pub fn borders(&self, node: usize) -> Vec<usize> {
+        let size = (self.width, self.height);
+        match self.position(node) {
+            Some(p) => Direction::ALL
+                .into_iter()
+                .filter(|side| p.step(*side, size).is_none())
+                .map(|side| self.border(side))
+                .collect(),
+            None => (0..self.width * self.height)
+                .filter(|cell| self.borders(*cell).contains(&node))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_sets() {
+        let mut sets = UnionFind::new(5);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(!sets.union(1, 0));
+        assert!(sets.connected(0, 1));
+        assert!(!sets.connected(1, 3));
+        assert_eq!(sets.sets(), 3);
+
+        sets.union(1, 4);
+        assert_eq!(sets.set_size(3), 4);
+        assert_eq!(sets.set_size(2), 1);
+    }
+
+    #[test]
+    fn finds_when_cells_get_disconnected() {
+        // a 3x3 grid, blocked from the middle of the top row down to the bottom.
+        let nodes = GridNodes::new((3, 3));
+        let cell = |x, y| nodes.cell(Position::new(x, y));
+        let blocked = [cell(1, 0), cell(0, 0), cell(1, 1), cell(1, 0), cell(1, 2)];
+
+        let mut timeline = Timeline::new(nodes.count(), |n| nodes.neighbours(n));
+        assert_eq!(
+            timeline.first_disconnection(&blocked, cell(0, 2), cell(2, 2)),
+            Some(4)
+        );
+        assert_eq!(
+            timeline.first_disconnection(&blocked, cell(2, 0), cell(0, 1)),
+            Some(4)
+        );
+        assert_eq!(
+            timeline.first_disconnection(&blocked, cell(2, 2), cell(1, 1)),
+            Some(2)
+        );
+        assert_eq!(
+            timeline.first_disconnection(&blocked, cell(0, 0), cell(0, 1)),
+            Some(1)
+        );
+        assert_eq!(
+            timeline.first_disconnection(&blocked, cell(2, 0), cell(2, 2)),
+            None
+        );
+    }
+
+    #[test]
+    fn finds_when_walls_connect_borders() {
+        // walls cut the grid once they connect the top border to the bottom one.
+        let nodes = GridNodes::new((3, 3));
+        let walls = [(0, 0), (2, 2), (1, 1), (0, 2)].map(|(x, y)| nodes.cell(Position::new(x, y)));
+
+        let mut timeline = Timeline::new(nodes.count(), |n| {
+            let mut neighbours: Vec<usize> = nodes.neighbours8(n).collect();
+            neighbours.extend(nodes.borders(n));
+            neighbours
+        });
+        let (top, bottom) = (nodes.border(Direction::Up), nodes.border(Direction::Down));
+        assert_eq!(timeline.first_connection(&walls, top, bottom), Some(2));
+        assert_eq!(nodes.borders(nodes.cell(Position::new(0, 0))).len(), 2);
+        assert_eq!(nodes.borders(top).len(), 3);
+    }
+}
@@ -0,0 +1,275 @@
+//! End-to-end tests of the commands. Each test runs in a copy of the template in a temporary
+//! directory, with the mock of aoc-cli in `tests/support/mock_aoc.rs` in place of the real site.
+//!
+//! Run with `cargo test --features test_lib --test end_to_end`. The first run builds the
+//! dependencies of the copies, which takes a while.
+#![cfg(feature = "test_lib")]
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const SOLUTION: &str = r#"advent_of_code::solution!(1);
+
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(input.lines().filter_map(|l| l.parse::<u64>().ok()).sum())
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    input.lines().filter_map(|l| l.parse::<u64>().ok()).max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(6));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(3));
+    }
+}
+"#;
+
+const README: &str = "# Advent of Code
+
+The stars below are listed between the `<!--- progress table --->` markers.
+
+<!--- progress table --->
+<!--- progress table --->
+
+<!--- benchmarking table --->
+<!--- benchmarking table --->
+";
+
+/// A copy of the template, with the files served by the mock in `mock/`.
+struct Project {
+    dir: PathBuf,
+}
+
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            copy_dir(&path, &to.join(entry.file_name()));
+        } else {
+            fs::copy(&path, to.join(entry.file_name())).unwrap();
+        }
+    }
+}
+
+impl Project {
+    fn new(name: &str) -> Self {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("e2e-{name}"));
+        let _ = fs::remove_dir_all(&dir);
+
+        for sub_dir in [
+            "src/bin",
+            "data/inputs",
+            "data/examples",
+            "data/puzzles",
+            "tests/support",
+            ".cargo",
+            "mock/inputs",
+            "mock/puzzles",
+            "mock/answers",
+            "mock/verdicts",
+        ] {
+            fs::create_dir_all(dir.join(sub_dir)).unwrap();
+        }
+
+        for file in [
+            "Cargo.toml",
+            "Cargo.lock",
+            "tests/support/mock_aoc.rs",
+            ".cargo/config.toml",
+        ] {
+            if root.join(file).exists() {
+                fs::copy(root.join(file), dir.join(file)).unwrap();
+            }
+        }
+        // the whole library, but none of the solutions : the tests scaffold their own days.
+        for entry in fs::read_dir(root.join("src")).unwrap().flatten() {
+            let (path, to) = (entry.path(), dir.join("src").join(entry.file_name()));
+            if path.is_dir() {
+                if entry.file_name() != "bin" {
+                    copy_dir(&path, &to);
+                }
+            } else {
+                fs::copy(&path, &to).unwrap();
+            }
+        }
+        fs::write(dir.join("README.md"), README).unwrap();
+
+        let project = Project { dir };
+        project.write("mock/inputs/01.txt", "10\n20\n30\n");
+        project.write("mock/answers/01.txt", "60\n30\n");
+        project.write(
+            "mock/puzzles/01.md",
+            "\\--- Day 1: Mock Puzzle ---\n----------\n\nAdd up the *numbers*.\n",
+        );
+        project.write(
+            "mock/puzzles/01-2.md",
+            "\\--- Part Two ---\n----------\n\nFind the *largest* number.\n",
+        );
+        project
+    }
+
+    fn path(&self, file: &str) -> PathBuf {
+        self.dir.join(file)
+    }
+
+    fn read(&self, file: &str) -> String {
+        fs::read_to_string(self.path(file)).unwrap_or_default()
+    }
+
+    fn write(&self, file: &str, contents: &str) {
+        fs::write(self.path(file), contents).unwrap();
+    }
+
+    /// Runs a command of the template, and returns its output (stdout and stderr).
+    fn run(&self, args: &[&str]) -> (bool, String) {
+        let output = Command::new(env!("CARGO_BIN_EXE_advent_of_code"))
+            .args(args)
+            .current_dir(&self.dir)
+            .env("AOC_CLI", env!("CARGO_BIN_EXE_mock_aoc"))
+            .env("MOCK_AOC_DIR", self.path("mock"))
+            .env("AOC_YEAR", "2024")
+            // shared by all copies, so dependencies are only built once.
+            .env(
+                "CARGO_TARGET_DIR",
+                Path::new(env!("CARGO_TARGET_TMPDIR")).join("e2e-target"),
+            )
+            .output()
+            .unwrap();
+
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        (output.status.success(), text)
+    }
+
+    /// Runs a command that must succeed.
+    fn run_ok(&self, args: &[&str]) -> String {
+        let (success, output) = self.run(args);
+        assert!(success, "`{}` failed:\n{output}", args.join(" "));
+        output
+    }
+}
+
+impl Drop for Project {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
+#[test]
+fn scaffold_download_solve_submit_time() {
+    let project = Project::new("flow");
+
+    project.run_ok(&["scaffold", "1"]);
+    assert!(project.path("src/bin/01.rs").exists());
+    project.write("src/bin/01.rs", SOLUTION);
+    project.write("data/examples/01.txt", "1\n2\n3\n");
+
+    let output = project.run_ok(&["download", "1"]);
+    assert!(output.contains("Successfully wrote input"), "{output}");
+    assert_eq!(project.read("data/inputs/01.txt"), "10\n20\n30\n");
+    assert!(project.read("data/puzzles/01.md").contains("Mock Puzzle"));
+
+    let output = project.run_ok(&["read", "1", "--no-pager"]);
+    assert!(output.contains("--- Day 1: Mock Puzzle ---"), "{output}");
+    assert!(!output.contains("Part Two"), "{output}");
+
+    // the first answer is sent too soon, and resubmitted after the wait.
+    project.write("mock/verdicts/01-1.txt", "too recent 1\n");
+    let output = project.run_ok(&["solve", "1", "--release", "--submit", "1", "--retry"]);
+    assert!(output.contains("That's the right answer"), "{output}");
+    assert_eq!(project.read("mock/submissions.log"), "01 1 60\n01 1 60\n");
+
+    let submissions = project.read("data/submissions.json");
+    assert!(submissions.contains("too recent"), "{submissions}");
+    assert!(submissions.contains("correct"), "{submissions}");
+
+    // part one is solved, so reading the puzzle picks up part two.
+    let output = project.run_ok(&["read", "1", "--no-pager"]);
+    assert!(output.contains("--- Part Two ---"), "{output}");
+
+    project.run_ok(&["solve", "1", "--release", "--submit", "2"]);
+    assert!(project.read("mock/submissions.log").ends_with("01 2 30\n"));
+
+    let output = project.run_ok(&["time", "1", "--store"]);
+    assert!(output.contains("Stored updated benchmarks."), "{output}");
+
+    let readme = project.read("README.md");
+    assert!(
+        readme.contains("| [Day 1](./src/bin/01.rs) | `"),
+        "{readme}"
+    );
+    assert!(readme.contains("Mock Puzzle"), "{readme}");
+    assert!(readme.contains("**Stars: 2/50**"), "{readme}");
+    // the marker quoted in prose is left alone.
+    assert!(
+        readme.contains("between the `<!--- progress table --->` markers.\n"),
+        "{readme}"
+    );
+    assert_eq!(readme.matches("## Progress").count(), 1, "{readme}");
+}
+
+#[test]
+fn rejected_answers_are_not_submitted() {
+    let project = Project::new("rejected");
+
+    project.run_ok(&["scaffold", "1"]);
+    project.write("src/bin/01.rs", SOLUTION);
+    project.write("data/examples/01.txt", "1\n2\n3\n");
+    // solving the example instead of the input gives the answer of the example.
+    project.write("data/inputs/01.txt", "1\n2\n3\n");
+
+    let (_, output) = project.run(&["solve", "1", "--release", "--submit", "1"]);
+    assert!(
+        output.contains("the answer is the one of the example"),
+        "{output}"
+    );
+    assert!(!project.path("mock/submissions.log").exists());
+}
+
+#[test]
+fn changed_inputs_are_kept() {
+    let project = Project::new("changed-input");
+
+    project.run_ok(&["download", "1"]);
+    project.write("mock/inputs/01.txt", "10\n20\n31\n");
+
+    let (success, output) = project.run(&["download", "1"]);
+    assert!(!success, "{output}");
+    assert!(output.contains("differs from"), "{output}");
+    assert_eq!(project.read("data/inputs/01.txt"), "10\n20\n30\n");
+    assert_eq!(project.read("data/inputs/01.txt.new"), "10\n20\n31\n");
+
+    let output = project.run_ok(&["download", "1", "--puzzle-only"]);
+    assert!(output.contains("is up to date"), "{output}");
+}
+
+#[test]
+fn locked_days_can_not_be_downloaded() {
+    let project = Project::new("locked");
+
+    let (success, output) = project.run(&["download", "2"]);
+    assert!(!success, "{output}");
+    assert!(!project.path("data/inputs/02.txt").exists());
+}
@@ -0,0 +1,220 @@
+//! A mock of aoc-cli for the end-to-end tests. It accepts the arguments the template passes to
+//! `aoc`, but serves the files of the directory in `MOCK_AOC_DIR` instead of adventofcode.com:
+//!
+//! - `inputs/<day>.txt` and `puzzles/<day>.md`: the input and description of a day. A day without
+//!   an input is not unlocked yet.
+//! - `puzzles/<day>-2.md`: the description of part two, appended once part one is solved.
+//! - `answers/<day>.txt`: the correct answers, one line per part.
+//! - `verdicts/<day>-<part>.txt`: scripted responses to the next submissions of a part, one per
+//!   line, which are used up before answers are checked: `too recent <seconds>`, `too high`,
+//!   `too low`, `incorrect` or `correct`.
+//!
+//! Submissions are appended to `submissions.log` as `<day> <part> <answer>`.
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process,
+};
+
+fn fail(message: &str) -> ! {
+    eprintln!("[mock aoc] {message}");
+    process::exit(1);
+}
+
+struct Args {
+    day: String,
+    command: String,
+    positional: Vec<String>,
+    input_file: Option<String>,
+    puzzle_file: Option<String>,
+    input_only: bool,
+    puzzle_only: bool,
+    overwrite: bool,
+}
+
+fn parse_args() -> Args {
+    let mut args = Args {
+        day: String::new(),
+        command: String::new(),
+        positional: vec![],
+        input_file: None,
+        puzzle_file: None,
+        input_only: false,
+        puzzle_only: false,
+        overwrite: false,
+    };
+
+    let mut raw = env::args().skip(1);
+    while let Some(arg) = raw.next() {
+        let mut value = || {
+            raw.next()
+                .unwrap_or_else(|| fail(&format!("{arg} needs a value")))
+        };
+        match arg.as_str() {
+            "-V" => {
+                println!("aoc-cli 0.0.0 (mock)");
+                process::exit(0);
+            }
+            "--day" => args.day = format!("{:02}", value().parse::<u8>().unwrap_or(0)),
+            "--year" => {
+                value();
+            }
+            "--input-file" => args.input_file = Some(value()),
+            "--puzzle-file" => args.puzzle_file = Some(value()),
+            "--input-only" => args.input_only = true,
+            "--puzzle-only" => args.puzzle_only = true,
+            "--overwrite" => args.overwrite = true,
+            "--description-only" => {}
+            _ if args.command.is_empty() => args.command = arg,
+            _ => args.positional.push(arg),
+        }
+    }
+
+    args
+}
+
+fn root() -> PathBuf {
+    PathBuf::from(env::var("MOCK_AOC_DIR").unwrap_or_else(|_| fail("MOCK_AOC_DIR is not set")))
+}
+
+fn is_solved(day: &str, part: u8) -> bool {
+    fs::read_to_string(root().join("solved.log"))
+        .unwrap_or_default()
+        .lines()
+        .any(|l| l == format!("{day} {part}"))
+}
+
+fn append(path: &Path, line: &str) {
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .unwrap();
+    writeln!(file, "{line}").unwrap();
+}
+
+/// The description of a day, with part two once part one is solved.
+fn puzzle(day: &str) -> String {
+    let mut puzzle = fs::read_to_string(root().join(format!("puzzles/{day}.md")))
+        .unwrap_or_else(|_| fail(&format!("day {day} is not unlocked (404)")));
+
+    if is_solved(day, 1) {
+        if let Ok(part_two) = fs::read_to_string(root().join(format!("puzzles/{day}-2.md"))) {
+            puzzle.push('\n');
+            puzzle.push_str(&part_two);
+        }
+    }
+
+    puzzle
+}
+
+fn save(path: &str, contents: &str, overwrite: bool) {
+    if !overwrite && Path::new(path).exists() {
+        fail(&format!("file '{path}' already exists, use '--overwrite'"));
+    }
+    fs::write(path, contents).unwrap_or_else(|e| fail(&format!("could not write '{path}': {e}")));
+    eprintln!("[mock aoc] saved '{path}'");
+}
+
+fn download(args: &Args) {
+    let input = fs::read_to_string(root().join(format!("inputs/{}.txt", args.day)))
+        .unwrap_or_else(|_| fail(&format!("day {} is not unlocked (404)", args.day)));
+
+    if !args.input_only {
+        let path = args.puzzle_file.as_deref().unwrap_or("puzzle.md");
+        save(path, &puzzle(&args.day), args.overwrite);
+    }
+    if !args.puzzle_only {
+        let path = args.input_file.as_deref().unwrap_or("input");
+        save(path, &input, args.overwrite);
+    }
+}
+
+fn read(args: &Args) {
+    let puzzle = puzzle(&args.day);
+    if let Some(path) = &args.puzzle_file {
+        save(path, &puzzle, true);
+    }
+    println!("{puzzle}");
+}
+
+/// The response of the server to an answer, scripted or checked against the correct answer.
+fn verdict(day: &str, part: u8, answer: &str) -> String {
+    let scripted_path = root().join(format!("verdicts/{day}-{part}.txt"));
+    let scripted = fs::read_to_string(&scripted_path).unwrap_or_default();
+    let mut lines = scripted.lines();
+
+    let verdict = match lines.next() {
+        Some(verdict) => {
+            let rest: Vec<&str> = lines.collect();
+            fs::write(&scripted_path, rest.join("\n")).unwrap();
+            verdict.to_string()
+        }
+        None => {
+            let answers =
+                fs::read_to_string(root().join(format!("answers/{day}.txt"))).unwrap_or_default();
+            let correct = answers
+                .lines()
+                .nth(usize::from(part - 1))
+                .unwrap_or_default();
+
+            match (answer.parse::<i64>(), correct.parse::<i64>()) {
+                _ if answer == correct => "correct".into(),
+                (Ok(answer), Ok(correct)) if answer > correct => "too high".into(),
+                (Ok(answer), Ok(correct)) if answer < correct => "too low".into(),
+                _ => "incorrect".into(),
+            }
+        }
+    };
+
+    if let Some(seconds) = verdict.strip_prefix("too recent") {
+        let seconds = Some(seconds.trim())
+            .filter(|s| !s.is_empty())
+            .unwrap_or("1");
+        return format!(
+            "You gave an answer too recently; you have to wait after submitting an answer \
+            before trying again. You have {seconds}s left to wait."
+        );
+    }
+
+    match verdict.as_str() {
+        "correct" => {
+            append(&root().join("solved.log"), &format!("{day} {part}"));
+            "That's the right answer! You are one gold star closer to finding the Chief Historian."
+                .into()
+        }
+        "too high" => "That's not the right answer; your answer is too high.".into(),
+        "too low" => "That's not the right answer; your answer is too low.".into(),
+        "incorrect" => "That's not the right answer.".into(),
+        other => fail(&format!("unknown scripted verdict '{other}'")),
+    }
+}
+
+fn submit(args: &Args) {
+    let [part, answer] = args.positional.as_slice() else {
+        fail("usage: aoc --day <day> submit <part> <answer>");
+    };
+    let part: u8 = part.parse().unwrap_or_else(|_| fail("invalid part"));
+
+    if part == 2 && !is_solved(&args.day, 1) {
+        println!("You don't seem to be solving the right level. Did you already complete it?");
+        return;
+    }
+
+    append(
+        &root().join("submissions.log"),
+        &format!("{} {part} {answer}", args.day),
+    );
+    println!("{}", verdict(&args.day, part, answer));
+}
+
+fn main() {
+    let args = parse_args();
+    match args.command.as_str() {
+        "download" | "d" => download(&args),
+        "read" | "r" => read(&args),
+        "submit" | "s" => submit(&args),
+        other => fail(&format!("unsupported command '{other}'")),
+    }
+}